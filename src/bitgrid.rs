@@ -0,0 +1,278 @@
+use std::fmt::Display;
+
+use crate::{grid::Grid, point::Point};
+
+/// A grid of booleans packed one bit per cell.  A grid may have several layers, giving each cell
+/// one bit per layer, for example one per direction when tracking both where a guard has been and
+/// which way they were facing.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    layers: usize,
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Create an empty grid with a single layer.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_layers(width, height, 1)
+    }
+
+    /// Create an empty grid with the given number of layers (bits per cell).
+    pub fn with_layers(width: usize, height: usize, layers: usize) -> Self {
+        assert!(layers > 0);
+        Self {
+            width,
+            height,
+            layers,
+            bits: vec![0; (width * height * layers).div_ceil(64)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn layers(&self) -> usize {
+        self.layers
+    }
+
+    /// The word and bit mask holding the given cell's layer, or None if it's out of bounds.
+    fn locate(&self, p: Point<2>, layer: usize) -> Option<(usize, u64)> {
+        let [x, y] = <[usize; 2]>::try_from(p).ok()?;
+        if x >= self.width || y >= self.height || layer >= self.layers {
+            return None;
+        }
+        let i = (y * self.width + x) * self.layers + layer;
+        Some((i / 64, 1 << (i % 64)))
+    }
+
+    /// Set the bit for the given point in layer 0.  Returns true if it wasn't already set.
+    /// Panics if the point is out of bounds.
+    pub fn set(&mut self, p: Point<2>) -> bool {
+        self.set_layer(p, 0)
+    }
+
+    /// Set the bit for the given point and layer.  Returns true if it wasn't already set.
+    /// Panics if the point or layer is out of bounds.
+    pub fn set_layer(&mut self, p: Point<2>, layer: usize) -> bool {
+        let (word, mask) = self
+            .locate(p, layer)
+            .unwrap_or_else(|| panic!("{p} layer {layer} is outside the grid"));
+        let was_set = self.bits[word] & mask != 0;
+        self.bits[word] |= mask;
+        !was_set
+    }
+
+    /// Check the bit for the given point in layer 0.  Points outside the grid are never set.
+    pub fn test(&self, p: Point<2>) -> bool {
+        self.test_layer(p, 0)
+    }
+
+    /// Check the bit for the given point and layer.  Points outside the grid are never set.
+    pub fn test_layer(&self, p: Point<2>, layer: usize) -> bool {
+        self.locate(p, layer)
+            .is_some_and(|(word, mask)| self.bits[word] & mask != 0)
+    }
+
+    /// Check whether the bit for the given point is set in any layer.
+    pub fn test_any(&self, p: Point<2>) -> bool {
+        (0..self.layers).any(|layer| self.test_layer(p, layer))
+    }
+
+    /// Clear the bit for the given point in layer 0.  Returns true if it was set.
+    pub fn clear(&mut self, p: Point<2>) -> bool {
+        self.clear_layer(p, 0)
+    }
+
+    /// Clear the bit for the given point and layer.  Returns true if it was set.
+    pub fn clear_layer(&mut self, p: Point<2>, layer: usize) -> bool {
+        if let Some((word, mask)) = self.locate(p, layer) {
+            let was_set = self.bits[word] & mask != 0;
+            self.bits[word] &= !mask;
+            was_set
+        } else {
+            false
+        }
+    }
+
+    /// Clear every bit in the grid.
+    pub fn clear_all(&mut self) {
+        self.bits.fill(0);
+    }
+
+    /// The number of bits set, across all layers.
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The number of cells with a bit set in any layer.
+    pub fn count_cells(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// A grid with the bits set in either grid.  Panics if the grids' shapes differ.
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// A grid with the bits set in both grids.  Panics if the grids' shapes differ.
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    fn zip_with(&self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) -> BitGrid {
+        assert_eq!(
+            (self.width, self.height, self.layers),
+            (other.width, other.height, other.layers)
+        );
+        BitGrid {
+            bits: self
+                .bits
+                .iter()
+                .zip(&other.bits)
+                .map(|(a, b)| f(*a, *b))
+                .collect(),
+            ..*self
+        }
+    }
+
+    /// Iterate over the positions of cells with a bit set in any layer, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = Point<2>> + '_ {
+        self.positions().filter(|p| self.test_any(*p))
+    }
+
+    /// Iterate over the positions of cells with a bit set in the given layer, in row order.
+    pub fn iter_layer(&self, layer: usize) -> impl Iterator<Item = Point<2>> + '_ {
+        self.positions().filter(move |p| self.test_layer(*p, layer))
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Point<2>> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| [x, y].try_into().unwrap()))
+    }
+
+    /// Convert to a grid of booleans, with cells true if a bit is set in any layer.
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::new(
+            (0..self.height)
+                .map(|y| {
+                    (0..self.width)
+                        .map(|x| self.test_any([x, y].try_into().unwrap()))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width(), grid.height());
+        for (y, row) in grid.cells.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell {
+                    bits.set([x, y].try_into().unwrap());
+                }
+            }
+        }
+        bits
+    }
+}
+
+/// Cells with a bit set in any layer are printed as `#`, and the rest as `.`.
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.test_any([x, y].try_into().unwrap()) {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod bitgrid_tests {
+    use super::*;
+
+    #[test]
+    fn set_test_clear_test() {
+        let mut g = BitGrid::new(10, 10);
+        assert!(!g.test([3, 4].into()));
+        assert!(g.set([3, 4].into()));
+        assert!(!g.set([3, 4].into()));
+        assert!(g.test([3, 4].into()));
+        assert!(!g.test([4, 3].into()));
+        assert!(!g.test([-1, 4].into()));
+        assert!(!g.test([10, 4].into()));
+        assert_eq!(g.count(), 1);
+        assert!(g.clear([3, 4].into()));
+        assert!(!g.clear([3, 4].into()));
+        assert_eq!(g.count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds_test() {
+        BitGrid::new(3, 3).set([3, 0].into());
+    }
+
+    #[test]
+    fn layers_test() {
+        let mut g = BitGrid::with_layers(5, 5, 4);
+        g.set_layer([1, 1].into(), 0);
+        g.set_layer([1, 1].into(), 3);
+        g.set_layer([4, 4].into(), 2);
+
+        assert!(g.test_layer([1, 1].into(), 3));
+        assert!(!g.test_layer([1, 1].into(), 2));
+        assert!(g.test_any([4, 4].into()));
+        assert!(!g.test_any([0, 0].into()));
+        assert_eq!(g.count(), 3);
+        assert_eq!(g.count_cells(), 2);
+        assert_eq!(
+            g.iter().collect::<Vec<Point<2>>>(),
+            vec![[1, 1].into(), [4, 4].into()]
+        );
+        assert_eq!(
+            g.iter_layer(2).collect::<Vec<Point<2>>>(),
+            vec![[4, 4].into()]
+        );
+    }
+
+    #[test]
+    fn union_intersection_test() {
+        let mut a = BitGrid::new(9, 9);
+        let mut b = BitGrid::new(9, 9);
+        a.set([0, 0].into());
+        a.set([8, 8].into());
+        b.set([8, 8].into());
+        b.set([4, 4].into());
+
+        assert_eq!(a.union(&b).count(), 3);
+        assert_eq!(
+            a.intersection(&b).iter().collect::<Vec<Point<2>>>(),
+            vec![[8, 8].into()]
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let g = Grid::from([[true, false, false], [false, true, true]]);
+        let bits = BitGrid::from(&g);
+
+        assert_eq!(format!("{bits}"), "#..\n.##\n");
+        assert_eq!(bits.to_grid(), g);
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+};
+
+use itertools::Itertools;
+
+use crate::graph::Graph;
+
+/// Parse an undirected graph from lines like `ab-cd`, each joining two named nodes.
+pub fn parse_edges(input: &str) -> Graph<&str> {
+    Graph::undirected_pairs(input.lines().filter(|line| !line.is_empty()).map(|line| {
+        line.split_once('-')
+            .unwrap_or_else(|| panic!("edge {line:?} should look like a-b"))
+    }))
+}
+
+/// Every set of three nodes that are all joined to each other.  Each triangle is sorted, and
+/// the list of triangles is too.
+pub fn triangles<N, E>(g: &Graph<N, E>) -> Vec<[N; 3]>
+where
+    N: Clone + Eq + Hash + Ord,
+    E: Clone,
+{
+    let adj = Adjacency::new(g);
+    let mut triangles = Vec::new();
+    // only count each triangle from its smallest node, going up
+    for (a, b) in adj.edges() {
+        for &c in adj.neighbors[b]
+            .iter()
+            .filter(|c| adj.nodes[**c] > adj.nodes[b])
+        {
+            if adj.neighbors[a].contains(&c) {
+                triangles.push([a, b, c].map(|i| adj.nodes[i].clone()));
+            }
+        }
+    }
+    triangles.sort();
+    triangles
+}
+
+/// Every maximal clique: sets of nodes that are all joined to each other, and can't be grown
+/// by adding another node.  Found by the Bron–Kerbosch algorithm with pivoting.  Each clique
+/// is sorted, and the list of cliques is too.
+pub fn maximal_cliques<N, E>(g: &Graph<N, E>) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash + Ord,
+    E: Clone,
+{
+    if g.is_empty() {
+        return vec![];
+    }
+
+    let adj = Adjacency::new(g);
+    let mut cliques = Vec::new();
+    adj.bron_kerbosch(
+        &mut Vec::new(),
+        (0..adj.nodes.len()).collect(),
+        HashSet::new(),
+        &mut cliques,
+    );
+
+    let mut cliques: Vec<Vec<N>> = cliques
+        .into_iter()
+        .map(|c| {
+            c.into_iter()
+                .map(|i| adj.nodes[i].clone())
+                .sorted()
+                .collect()
+        })
+        .collect();
+    cliques.sort();
+    cliques
+}
+
+/// The largest clique, sorted.  If several are the same size, the first in sorted order wins.
+/// Empty for an empty graph.
+pub fn maximum_clique<N, E>(g: &Graph<N, E>) -> Vec<N>
+where
+    N: Clone + Eq + Hash + Ord,
+    E: Clone,
+{
+    maximal_cliques(g)
+        .into_iter()
+        .rev()
+        .max_by_key(|c| c.len())
+        .unwrap_or_default()
+}
+
+/// The names of a set of nodes, sorted and joined with commas, eg `co,de,ka,ta`.
+pub fn join_sorted<N: Display>(nodes: impl IntoIterator<Item = N>) -> String {
+    nodes.into_iter().map(|n| n.to_string()).sorted().join(",")
+}
+
+/// Neighbor sets by node index, for fast membership tests.
+struct Adjacency<'a, N> {
+    nodes: Vec<&'a N>,
+    neighbors: Vec<HashSet<usize>>,
+}
+
+impl<'a, N: Eq + Hash + Ord> Adjacency<'a, N> {
+    fn new<E: Clone>(g: &'a Graph<N, E>) -> Self
+    where
+        N: Clone,
+    {
+        let nodes: Vec<&N> = g.nodes().collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let neighbors = nodes
+            .iter()
+            .map(|n| {
+                g.neighbors(n)
+                    .map(|(m, _)| index[m])
+                    .filter(|m| nodes[*m] != *n)
+                    .collect()
+            })
+            .collect();
+        Self { nodes, neighbors }
+    }
+
+    /// Each edge once, from its smaller node to its larger.
+    fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.nodes.len()).flat_map(move |a| {
+            self.neighbors[a]
+                .iter()
+                .filter(move |b| self.nodes[**b] > self.nodes[a])
+                .map(move |b| (a, *b))
+        })
+    }
+
+    /// Report every maximal clique that contains all of `r`, some of `p`, and none of `x`.
+    fn bron_kerbosch(
+        &self,
+        r: &mut Vec<usize>,
+        mut p: HashSet<usize>,
+        mut x: HashSet<usize>,
+        cliques: &mut Vec<Vec<usize>>,
+    ) {
+        if p.is_empty() && x.is_empty() {
+            cliques.push(r.clone());
+            return;
+        }
+
+        // any maximal clique includes the pivot or one of its non-neighbors, so only those
+        // need trying; picking the pivot with the most neighbors in p prunes the most
+        let pivot = *p
+            .union(&x)
+            .max_by_key(|u| self.neighbors[**u].intersection(&p).count())
+            .unwrap();
+        let candidates: Vec<usize> = p.difference(&self.neighbors[pivot]).copied().collect();
+
+        for v in candidates {
+            r.push(v);
+            self.bron_kerbosch(
+                r,
+                p.intersection(&self.neighbors[v]).copied().collect(),
+                x.intersection(&self.neighbors[v]).copied().collect(),
+                cliques,
+            );
+            r.pop();
+            p.remove(&v);
+            x.insert(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod clique_tests {
+    use super::*;
+
+    const LAN: &str = "kh-tc\nqp-kh\nde-cg\nka-co\nyn-aq\nqp-ub\ncg-tb\nvc-aq\ntb-ka\nwh-tc\n\
+        yn-cg\nkh-ub\nta-co\nde-co\ntc-td\ntb-wq\nwh-td\nta-ka\ntd-qp\naq-cg\nwq-ub\nub-vc\n\
+        de-ta\nwq-aq\nwq-vc\nwh-yn\nka-de\nkh-ta\nco-tc\nwh-qp\ntb-vc\ntd-yn\n";
+
+    /// Whether every pair of the nodes is joined.
+    fn is_clique(g: &Graph<&str>, nodes: &[&str]) -> bool {
+        nodes
+            .iter()
+            .tuple_combinations()
+            .all(|(a, b)| g.has_edge(a, b))
+    }
+
+    #[test]
+    fn triangles_test() {
+        let g = parse_edges(LAN);
+        let triangles = triangles(&g);
+        assert_eq!(triangles.len(), 12);
+        assert!(triangles.contains(&["co", "de", "ta"]));
+
+        // compare with checking every triple
+        let brute = g
+            .nodes()
+            .copied()
+            .sorted()
+            .tuple_combinations()
+            .filter(|(a, b, c)| is_clique(&g, &[a, b, c]))
+            .count();
+        assert_eq!(triangles.len(), brute);
+
+        let with_t = triangles
+            .iter()
+            .filter(|t| t.iter().any(|n| n.starts_with('t')))
+            .count();
+        assert_eq!(with_t, 7);
+    }
+
+    #[test]
+    fn maximal_cliques_test() {
+        let g = parse_edges(LAN);
+        let cliques = maximal_cliques(&g);
+        for c in &cliques {
+            assert!(is_clique(&g, c));
+            // maximal: no other node joins all of it
+            assert!(!g
+                .nodes()
+                .filter(|n| !c.contains(n))
+                .any(|n| c.iter().all(|m| g.has_edge(n, m))));
+        }
+        // every edge is in some maximal clique
+        assert!(g.nodes().all(|a| g
+            .neighbors(a)
+            .all(|(b, _)| cliques.iter().any(|c| c.contains(a) && c.contains(b)))));
+    }
+
+    #[test]
+    fn maximum_clique_test() {
+        let g = parse_edges(LAN);
+        assert_eq!(join_sorted(maximum_clique(&g)), "co,de,ka,ta");
+
+        // two disjoint squares with diagonals; ties go to the first in sorted order
+        let g = Graph::undirected_pairs(
+            ["a", "b", "c", "d"]
+                .into_iter()
+                .tuple_combinations()
+                .chain(["w", "x", "y", "z"].into_iter().tuple_combinations()),
+        );
+        assert_eq!(maximum_clique(&g), ["a", "b", "c", "d"]);
+        assert_eq!(maximal_cliques(&g).len(), 2);
+
+        assert!(maximal_cliques(&Graph::<&str>::new(false)).is_empty());
+        assert!(maximum_clique(&Graph::<&str>::new(false)).is_empty());
+    }
+
+    #[test]
+    fn join_sorted_test() {
+        assert_eq!(join_sorted(["ta", "co", "ka", "de"]), "co,de,ka,ta");
+        assert_eq!(join_sorted([3, 1, 2]), "1,2,3");
+        assert_eq!(join_sorted(Vec::<&str>::new()), "");
+    }
+}
@@ -0,0 +1,205 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A cycle in a sequence of states: state `start` is the first state that repeats, and the
+/// states repeat every `len` steps from there on.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Map a step number onto the earliest step with the same state.  Steps before the cycle
+    /// starts map to themselves.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Find the cycle reached by repeatedly applying `step` to `start`, by remembering every state
+/// until one repeats.  Never returns if the states don't repeat.
+pub fn detect<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    try_detect(start, |s| Some(step(s))).unwrap()
+}
+
+/// Like [detect], for simulations that may end.  Returns None if `step` returns None before any
+/// state repeats.
+pub fn try_detect<S: Clone + Eq + Hash>(
+    start: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = start;
+
+    for n in 0.. {
+        if let Some(first) = seen.get(&state) {
+            return Some(Cycle {
+                start: *first,
+                len: n - first,
+            });
+        }
+        let next = step(&state)?;
+        seen.insert(state, n);
+        state = next;
+    }
+
+    unreachable!()
+}
+
+/// Find the cycle reached by repeatedly applying `step` to `start`, using Brent's algorithm.
+/// Only a couple of states are held at once, so this suits states that are large or can't be
+/// hashed, at the cost of calling `step` a few more times than [detect] does.
+pub fn brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the cycle length by racing a hare ahead of a tortoise that teleports to the hare
+    // at every power of two
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // find the start of the cycle by walking two states `len` steps apart until they meet
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+
+    let mut first = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        first += 1;
+    }
+
+    Cycle { start: first, len }
+}
+
+/// Find the state after `n` applications of `step`, so `n` can be far larger than could be
+/// simulated directly.  States are remembered in order until one repeats, as in [detect], and
+/// `n` is reduced onto the cycle to pick out one of them.
+pub fn state_at<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+
+    // no need to look for a cycle past step n
+    for i in 0..n {
+        if let Some(&first) = seen.get(&state) {
+            let cycle = Cycle {
+                start: first,
+                len: i - first,
+            };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod cycle_tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 5, 6, 3, 4, 5, 6, ...
+    fn rho(n: &u32) -> u32 {
+        if *n < 6 {
+            n + 1
+        } else {
+            3
+        }
+    }
+
+    #[test]
+    fn detect_test() {
+        assert_eq!(detect(0, rho), Cycle { start: 3, len: 4 });
+        assert_eq!(detect(5, rho), Cycle { start: 0, len: 4 });
+        assert_eq!(detect(7, |n| *n), Cycle { start: 0, len: 1 });
+    }
+
+    #[test]
+    fn try_detect_test() {
+        assert_eq!(
+            try_detect(0, |n| Some(rho(n))),
+            Some(Cycle { start: 3, len: 4 })
+        );
+        assert_eq!(try_detect(0, |n| (*n < 10).then_some(n + 1)), None);
+
+        // step isn't called on the state that turns out to repeat
+        let mut calls = 0;
+        try_detect(0, |n| {
+            calls += 1;
+            Some(rho(n))
+        });
+        assert_eq!(calls, 7);
+    }
+
+    #[test]
+    fn brent_test() {
+        assert_eq!(brent(0, rho), Cycle { start: 3, len: 4 });
+        assert_eq!(brent(5, rho), Cycle { start: 0, len: 4 });
+        assert_eq!(brent(7, |n| *n), Cycle { start: 0, len: 1 });
+
+        // a long tail and a long cycle: 0..1000 then 1000..1337 repeating
+        let f = |n: &u64| if *n == 1336 { 1000 } else { n + 1 };
+        assert_eq!(
+            brent(0, f),
+            Cycle {
+                start: 1000,
+                len: 337
+            }
+        );
+        assert_eq!(brent(0, f), detect(0, f));
+    }
+
+    #[test]
+    fn reduce_test() {
+        let c = Cycle { start: 3, len: 4 };
+        assert_eq!(c.reduce(2), 2);
+        assert_eq!(c.reduce(3), 3);
+        assert_eq!(c.reduce(7), 3);
+        assert_eq!(c.reduce(10), 6);
+    }
+
+    #[test]
+    fn state_at_test() {
+        for n in 0..50 {
+            let mut expected = 0;
+            for _ in 0..n {
+                expected = rho(&expected);
+            }
+            assert_eq!(state_at(0, rho, n), expected);
+        }
+
+        assert_eq!(state_at(0, rho, 1_000_000_000_000), 4);
+
+        // states that never repeat are fine as long as n is small
+        assert_eq!(state_at(0u64, |n| n + 1, 10), 10);
+
+        // step is only called until the cycle is found
+        let mut calls = 0;
+        let step = |n: &u32| {
+            calls += 1;
+            rho(n)
+        };
+        assert_eq!(state_at(0, step, 1_000_000_000_000), 4);
+        assert_eq!(calls, 7);
+    }
+}
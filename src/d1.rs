@@ -3,19 +3,18 @@
 
 use std::collections::HashMap;
 
+use crate::parse::lines;
+
 type Model = (Vec<u32>, Vec<u32>);
 type Answer = u32;
 
 pub fn parse(input: String) -> Model {
-    let mut col1 = Vec::new();
-    let mut col2 = Vec::new();
-    input.lines().for_each(|l| {
-        let nums = l.split_once("   ").unwrap();
-        col1.push(nums.0.parse().unwrap());
-        col2.push(nums.1.parse().unwrap());
-    });
-
-    (col1, col2)
+    lines(&input)
+        .map(|line| line.pair::<u32, u32>("   "))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .into_iter()
+        .unzip()
 }
 
 pub fn part1(mut model: Model) -> Answer {
@@ -4,9 +4,13 @@
 use std::collections::HashSet;
 
 use itertools::Itertools;
-use termion::{color, style};
+use termion::color;
 
-use crate::{grid::Grid, point::Point};
+use crate::{
+    bitgrid::BitGrid,
+    grid::{Cardinal, Grid, Layer, Renderer},
+    point::Point,
+};
 
 type Model = Map;
 type Answer = usize;
@@ -23,7 +27,7 @@ pub fn parse(input: String) -> Model {
                     .enumerate()
                     .map(|(x, c)| {
                         if c == '0' {
-                            trailheads.push([x, y].into())
+                            trailheads.push([x, y].try_into().unwrap())
                         }
                         c.to_digit(10).unwrap() as u8
                     })
@@ -49,7 +53,7 @@ pub fn part1(model: Model) -> Answer {
             let mut peaks = Vec::new();
 
             #[cfg(feature = "visualize")]
-            let mut steps = [*trailhead].into();
+            let mut steps = model.new_steps(*trailhead);
 
             model.search(
                 *trailhead,
@@ -76,7 +80,7 @@ pub fn part2(model: Model) -> Answer {
             let mut peaks = Vec::new();
 
             #[cfg(feature = "visualize")]
-            let mut steps = [*trailhead].into();
+            let mut steps = model.new_steps(*trailhead);
 
             model.search(
                 *trailhead,
@@ -97,44 +101,43 @@ pub struct Map {
 }
 
 impl Map {
+    /// Create a record of the steps taken along a trail, beginning at the trailhead.
+    fn new_steps(&self, trailhead: Point<2>) -> BitGrid {
+        let mut steps = BitGrid::new(self.topography.width(), self.topography.height());
+        steps.set(trailhead);
+        steps
+    }
+
     fn search(
         &self,
         loc: Point<2>,
         mut peaks: &mut Vec<Point<2>>,
-        #[cfg(feature = "visualize")] mut steps: &mut HashSet<Point<2>>,
+        #[cfg(feature = "visualize")] mut steps: &mut BitGrid,
         #[cfg(feature = "visualize")] mut all_peaks: &mut HashSet<Point<2>>,
     ) {
         let cur = self.topography.getp(loc).unwrap();
 
         #[cfg(feature = "visualize")]
         {
-            std::thread::sleep_ms(10);
+            std::thread::sleep(std::time::Duration::from_millis(10));
             println!("{}", termion::clear::All);
-            let mut output = format!("{}", self.topography);
-            for (y, line) in output.lines().enumerate() {
-                for (x, c) in line.chars().enumerate() {
-                    let current_loc = loc.x() == x as i64 && loc.y() == y as i64;
-
-                    let whitebg = color::Bg(color::LightWhite);
-                    let grey = color::Fg(color::LightBlack);
-                    let greybg = color::Bg(color::LightBlack);
-                    let blackfg = color::Fg(color::Black);
-                    let reset = style::Reset;
-                    let green = color::Bg(color::Green);
-
-                    if (current_loc && cur == 9) || all_peaks.contains(&[x, y].into()) {
-                        print!("{green}{c}{reset}",);
-                    } else if current_loc {
-                        print!("{whitebg}{blackfg}{c}{reset}",);
-                    } else if steps.contains(&[x, y].into()) {
-                        print!("{greybg}{blackfg}{c}{reset}",);
-                    } else {
-                        print!("{grey}{c}{reset}",);
-                    }
-                }
 
-                println!();
-            }
+            let everywhere = (0..self.topography.width())
+                .cartesian_product(0..self.topography.height())
+                .map(|(x, y)| [x, y].try_into().unwrap());
+            let peaks = all_peaks.iter().copied().chain((cur == 9).then_some(loc));
+
+            Renderer::new(&self.topography, |height| char::from(b'0' + height))
+                .layer(Layer::new(everywhere).fg(color::LightBlack))
+                .layer(
+                    Layer::new(steps.iter())
+                        .fg(color::Black)
+                        .bg(color::LightBlack),
+                )
+                .layer(Layer::new([loc]).fg(color::Black).bg(color::LightWhite))
+                .layer(Layer::new(peaks).fg(color::Reset).bg(color::Green))
+                .print()
+                .unwrap();
         }
 
         if cur == 9 {
@@ -143,19 +146,17 @@ impl Map {
             #[cfg(feature = "visualize")]
             {
                 all_peaks.insert(loc);
-                std::thread::sleep_ms(10);
+                std::thread::sleep(std::time::Duration::from_millis(10));
             }
         } else {
-            let moves = self.topography.adj_4(loc);
-
             let mut score = 0;
 
-            moves.cells.into_iter().flatten().for_each(|cell| {
+            for (_dir, cell) in self.topography.neighbors(loc, Cardinal) {
                 if cur + 1 == cell.data {
                     #[cfg(feature = "visualize")]
                     let mut steps = {
                         let mut steps = steps.clone();
-                        steps.insert(cell.pos);
+                        steps.set(cell.pos);
                         steps
                     };
 
@@ -168,7 +169,7 @@ impl Map {
                         all_peaks,
                     );
                 }
-            });
+            }
         }
     }
 }
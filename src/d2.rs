@@ -3,14 +3,16 @@
 
 use std::ops::Sub;
 
+use crate::parse::lines;
+
 type Model = Vec<Vec<i32>>;
 type Answer = usize;
 
 pub fn parse(input: String) -> Model {
-    input
-        .lines()
-        .map(|l| l.split_whitespace().map(|n| n.parse().unwrap()).collect())
-        .collect()
+    lines(&input)
+        .map(|line| line.words())
+        .collect::<Result<_, _>>()
+        .unwrap()
 }
 
 pub fn part1(model: Model) -> Answer {
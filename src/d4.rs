@@ -1,7 +1,11 @@
 //! A solution to day 4 year 2024.
 //! https://adventofcode.com/2024/day/4
 
-use crate::{direction::CardOrdDir, grid::Grid, point::Point};
+use crate::{
+    direction::CardOrdDir,
+    grid::{Grid, Kernel, Symmetry},
+    point::Point,
+};
 
 type Model = (Grid<Letter>, Vec<Point<2>>);
 type Answer = usize;
@@ -18,7 +22,7 @@ pub fn parse(input: String) -> Model {
                 .map(|(x, c)| {
                     let letter = c.into();
                     if letter == Letter::X {
-                        xlocs.push([x, y].into());
+                        xlocs.push([x, y].try_into().unwrap());
                     }
                     letter
                 })
@@ -32,82 +36,30 @@ pub fn parse(input: String) -> Model {
 }
 
 pub fn part1(model: Model) -> Answer {
-    let mut sum = 0;
+    use Letter::*;
 
-    for xloc in model.1 {
-        for dir in CardOrdDir::all() {
-            sum += search_p1(&model.0, Letter::X, xloc, dir);
-        }
-    }
+    let (grid, xlocs) = model;
 
-    sum
+    xlocs
+        .iter()
+        .flat_map(|xloc| CardOrdDir::all().map(|dir| (*xloc, dir)))
+        .filter(|(xloc, dir)| {
+            grid.ray(*xloc, *dir)
+                .map(|cell| cell.data)
+                .take(4)
+                .eq([X, M, A, S])
+        })
+        .count()
 }
 
 pub fn part2(model: Model) -> Answer {
     search_p2(&model.0)
 }
 
-fn search_p1(grid: &Grid<Letter>, letter: Letter, point: Point<2>, dir: CardOrdDir) -> usize {
-    if letter == Letter::S {
-        return 1;
-    }
-
-    // get the next letter in the given direction
-    // at S, return count + 1
-
-    let next_point = point.move_in_grid_diag(dir, grid);
-
-    let next = next_point.and_then(|p| grid.getp(p));
-
-    if let Some(letter_in_line) = next {
-        if letter_in_line == letter.next() {
-            search_p1(grid, letter.next(), next_point.unwrap(), dir)
-        } else {
-            0
-        }
-    } else {
-        0
-    }
-}
-
 fn search_p2(grid: &Grid<Letter>) -> usize {
-    use Letter::*;
-
-    let kernels = [
-        [
-            [Some(M), None, Some(M)],
-            [None, Some(A), None],
-            [Some(S), None, Some(S)],
-        ],
-        [
-            [Some(S), None, Some(M)],
-            [None, Some(A), None],
-            [Some(S), None, Some(M)],
-        ],
-        [
-            [Some(S), None, Some(S)],
-            [None, Some(A), None],
-            [Some(M), None, Some(M)],
-        ],
-        [
-            [Some(M), None, Some(S)],
-            [None, Some(A), None],
-            [Some(M), None, Some(S)],
-        ],
-    ];
-
-    let mut sum = 0;
-    for kernel in kernels {
-        for x in 0..grid.width() - 2 {
-            for y in 0..grid.height() - 2 {
-                if grid.match_kernel(kernel, [x, y].into()) {
-                    sum += 1;
-                }
-            }
-        }
-    }
+    let kernel = Kernel::from_pattern("M.M\n.A.\nS.S", '.', Letter::from);
 
-    sum
+    grid.find_kernel(&kernel, Symmetry::Rotations).len()
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -119,18 +71,6 @@ pub enum Letter {
     Other,
 }
 
-impl Letter {
-    fn next(&self) -> Letter {
-        match self {
-            Letter::X => Letter::M,
-            Letter::M => Letter::A,
-            Letter::A => Letter::S,
-            Letter::S => Letter::Other,
-            Letter::Other => Letter::Other,
-        }
-    }
-}
-
 impl From<char> for Letter {
     fn from(value: char) -> Self {
         use Letter::*;
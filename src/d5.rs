@@ -1,26 +1,32 @@
 //! A solution to day 5 year 2024.
 //! https://adventofcode.com/2024/day/5
 
-use std::{cmp::Ordering, collections::HashSet};
+use std::iter;
+
+use itertools::Itertools;
+
+use crate::{graph::Graph, parse::sections};
 
 type Model = PrintPlan;
 type Answer = u32;
 
 pub fn parse(input: String) -> Model {
-    let (ord, prod) = input.split_once("\n\n").unwrap();
+    let [ord, prod] = &sections(&input)[..] else {
+        panic!("expected ordering rules and updates, separated by a blank line");
+    };
 
-    let ord = ord
-        .lines()
-        .map(|line| {
-            let (a, b) = line.split_once('|').unwrap();
-            (a.parse().unwrap(), b.parse().unwrap())
-        })
-        .collect();
+    let ord = Graph::directed_pairs(
+        ord.iter()
+            .map(|line| line.pair("|"))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+    );
 
     let prod = prod
-        .lines()
-        .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
-        .collect();
+        .iter()
+        .map(|line| line.list(","))
+        .collect::<Result<_, _>>()
+        .unwrap();
 
     Model { ord, print: prod }
 }
@@ -38,7 +44,8 @@ pub fn part2(model: Model) -> Answer {
 
 #[derive(Debug)]
 pub struct PrintPlan {
-    ord: HashSet<(u32, u32)>,
+    /// The ordering rules, with an edge from each page to a page that must come after it.
+    ord: Graph<u32>,
     print: Vec<Vec<u32>>,
 }
 
@@ -48,7 +55,7 @@ impl Model {
             return true;
         }
 
-        if !self.ord.contains(&(pages[0], pages[1])) {
+        if !self.ord.has_edge(&pages[0], &pages[1]) {
             return false;
         }
 
@@ -69,18 +76,22 @@ impl Model {
             .map(|run| self.fix_order(run))
     }
 
+    /// Put the pages in an order that follows every rule between them.  The full set of rules
+    /// has cycles, but the rules between the pages of any one run don't.  Pages with no rules
+    /// are kept, and a page printed more than once keeps every copy.
     fn fix_order(&self, pages: &[u32]) -> Vec<u32> {
-        let mut sorted = pages.to_vec();
-
-        sorted.sort_by(|a, b| {
-            if self.ord.contains(&(*a, *b)) {
-                Ordering::Less
-            } else {
-                Ordering::Greater
-            }
-        });
+        let mut rules = self.ord.subgraph(pages);
+        for page in pages {
+            rules.add_node(*page);
+        }
 
-        sorted
+        let copies = pages.iter().counts();
+        rules
+            .topological_sort()
+            .expect("the rules for a print run shouldn't have a cycle")
+            .into_iter()
+            .flat_map(|page| iter::repeat_n(page, copies[&page]))
+            .collect()
     }
 }
 
@@ -106,6 +117,22 @@ mod tests {
         assert_eq!(part2(parse(EXAMPLE.to_string())), 123);
     }
 
+    #[test]
+    fn d5p2_unruled_page_test() {
+        let model = parse("1|2\n2|3\n\n3,9,2,1\n3,1,3\n".to_string());
+
+        // 9 has no rules, so it can go anywhere, but it mustn't be dropped
+        let fixed = model.fix_order(&model.print[0]);
+        assert_eq!(fixed.iter().sorted().collect_vec(), [&1, &2, &3, &9]);
+        assert_eq!(
+            fixed.iter().filter(|p| **p != 9).collect_vec(),
+            [&1, &2, &3]
+        );
+
+        // both copies of a repeated page are kept
+        assert_eq!(model.fix_order(&model.print[1]), [1, 3, 3]);
+    }
+
     #[test]
     fn d5p2_input_test() {
         assert_eq!(part2(parse(INPUT.to_string())), 4598);
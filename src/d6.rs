@@ -3,15 +3,20 @@
 
 #![warn(unused)]
 
-use std::{collections::HashSet, fmt::Display};
+use std::{collections::HashSet, fmt::Display, iter};
 
-use crate::{direction::CardDir, grid::Grid, point::Point};
+use crate::{
+    bitgrid::BitGrid,
+    direction::{CardDir, Heading},
+    grid::Grid,
+    point::Point,
+};
 
 type Model = Map;
 type Answer = usize;
 
 pub fn parse(input: String) -> Model {
-    let mut guard: (Point<2>, CardDir) = ([0usize, 0].into(), CardDir::Up);
+    let mut guard = Heading::new([0, 0], CardDir::Up);
 
     let cells = input
         .lines()
@@ -22,8 +27,7 @@ pub fn parse(input: String) -> Model {
                 .map(|(x, c)| {
                     let spot = Spot::from(c);
                     if let Spot::Guard(d) = spot {
-                        guard.0.coords = [x as i64, y as i64];
-                        guard.1 = d;
+                        guard = Heading::new(Point::try_from([x, y]).unwrap(), d);
                         Spot::Empty
                     } else {
                         spot
@@ -33,9 +37,13 @@ pub fn parse(input: String) -> Model {
         })
         .collect();
 
+    let grid = Grid::new(cells);
+    let mut visited = BitGrid::with_layers(grid.width(), grid.height(), 4);
+    visited.set_layer(guard.pos, guard.dir as usize);
+
     Map {
-        grid: Grid::new(cells),
-        visited: [guard].into(),
+        grid,
+        visited,
         guard,
     }
 }
@@ -46,41 +54,32 @@ pub fn part1(mut model: Model) -> Answer {
         // std::thread::sleep_ms(100);
     }
 
-    model
-        .visited
-        .iter()
-        .map(|p| p.0)
-        .collect::<HashSet<Point<2>>>()
-        .len()
+    model.visited.count_cells()
 }
 
 pub fn part2(mut model: Model) -> Answer {
-    let model2 = model.clone();
+    let start = model.guard;
 
     while model.next().is_some() {}
 
-    let mut obs: HashSet<(usize, usize)> = HashSet::new();
+    // only spots on the guard's original route can change it, and the guard's starting position
+    // can't be obstructed
+    let route: Vec<Point<2>> = model.visited.iter().filter(|p| *p != start.pos).collect();
 
-    for pair in model
-        .visited
+    // every candidate shares one grid and one visited set, which are reset after each
+    route
         .into_iter()
-        .skip(1) // skip guard pos
-        .collect::<Vec<(Point<2>, CardDir)>>()
-    {
-        let mut submodel = model2.clone();
-        submodel.grid.setp(pair.0, Spot::Obstacle);
-
-        while let Some(seen) = submodel.next() {
-            // std::thread::sleep_ms(2);
-            // println!("\n\n{submodel}");
-            if seen {
-                obs.insert((pair.0.x() as usize, pair.0.y() as usize));
-                break;
-            }
-        }
-    }
-
-    obs.len()
+        .filter(|pos| {
+            model.grid.setp(*pos, Spot::Obstacle);
+            model.guard = start;
+            model.visited.clear_all();
+            model.visited.set_layer(start.pos, start.dir as usize);
+
+            let looped = iter::from_fn(|| model.next()).any(|seen| seen);
+            model.grid.setp(*pos, Spot::Empty);
+            looped
+        })
+        .count()
 }
 
 /// returns true if the model produces an infinite loop
@@ -116,8 +115,9 @@ pub fn search_p2(
 #[derive(Debug, Clone)]
 pub struct Map {
     grid: Grid<Spot>,
-    visited: HashSet<(Point<2>, CardDir)>,
-    guard: (Point<2>, CardDir),
+    /// Positions the guard has occupied, with one layer per direction they were facing.
+    visited: BitGrid,
+    guard: Heading,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -132,22 +132,23 @@ impl Map {
     /// Some(false) means it's a new position+direction.
     /// None means we left the grid.
     fn next(&mut self) -> Option<bool> {
-        let new_pos = self.guard.0.move_in_grid(self.guard.1, &self.grid)?;
-        let new_spot = self.grid.getp(new_pos)?;
+        self.guard = self.step(self.guard)?;
 
-        match new_spot {
-            Spot::Obstacle => self.guard.1 = self.guard.1.cw(),
-            Spot::Empty => {
-                self.guard.0 = new_pos;
-            }
-            _ => (),
-        }
+        let seen = !self
+            .visited
+            .set_layer(self.guard.pos, self.guard.dir as usize);
 
-        let seen = self.visited.contains(&self.guard);
+        Some(seen)
+    }
 
-        self.visited.insert(self.guard);
+    /// Where the guard will be after their next move (or turn), or None if they leave the map.
+    fn step(&self, guard: Heading) -> Option<Heading> {
+        let ahead = guard.step();
 
-        Some(seen)
+        match self.grid.getp(ahead.pos)? {
+            Spot::Obstacle => Some(guard.turn_right()),
+            _ => Some(ahead),
+        }
     }
 
     /// try to put an obstacle in front of the guard if the spot is empty.  
@@ -155,7 +156,7 @@ impl Map {
         &mut self,
         obs_list: &HashSet<Point<2>>,
     ) -> Option<(Point<2>, HashSet<Point<2>>)> {
-        if let Some(new_pos) = self.guard.0.move_in_grid(self.guard.1, &self.grid) {
+        if let Some(new_pos) = self.guard.pos.move_in_grid(self.guard.dir, &self.grid) {
             if matches!(self.grid.getp(new_pos), Some(Spot::Empty)) && !obs_list.contains(&new_pos)
             {
                 self.grid.setp(new_pos, Spot::Obstacle);
@@ -174,7 +175,7 @@ impl Map {
 impl From<char> for Spot {
     fn from(value: char) -> Self {
         match value {
-            '^' | '<' | '>' | 'v' => Spot::Guard(CardDir::from(value)),
+            '^' | '<' | '>' | 'v' => Spot::Guard(value.try_into().unwrap()),
             '#' => Spot::Obstacle,
             _ => Spot::Empty,
         }
@@ -185,11 +186,11 @@ impl Display for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (y, row) in self.grid.cells.iter().enumerate() {
             for (x, c) in row.iter().enumerate() {
-                if self.guard.0 == [x, y].into() {
+                if self.guard.pos == [x, y].try_into().unwrap() {
                     write!(
                         f,
                         "{}",
-                        match self.guard.1 {
+                        match self.guard.dir {
                             CardDir::Up => "^",
                             CardDir::Down => "v",
                             CardDir::Left => "<",
@@ -197,11 +198,7 @@ impl Display for Map {
                         }
                     )?;
                 } else if let Spot::Guard(_dir) = c {
-                } else if self.visited.contains(&([x, y].into(), CardDir::Up))
-                    || self.visited.contains(&([x, y].into(), CardDir::Right))
-                    || self.visited.contains(&([x, y].into(), CardDir::Down))
-                    || self.visited.contains(&([x, y].into(), CardDir::Left))
-                {
+                } else if self.visited.test_any([x, y].try_into().unwrap()) {
                     write!(f, "+")?;
                 } else {
                     match c {
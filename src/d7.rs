@@ -1,42 +1,42 @@
 //! A solution to day 7 year 2024.
 //! https://adventofcode.com/2024/day/7
 
-use cached::proc_macro::cached;
-use itertools::Itertools;
-use std::ops::Deref;
+use std::{ops::Deref, rc::Rc};
+
+use crate::{
+    memo::{FxBuildHasher, Memo},
+    num,
+    parse::{lines, ParseError},
+};
 
 type Model = Vec<Eqn>;
 type Answer = u64;
 
 pub fn parse(input: String) -> Model {
-    input
-        .lines()
-        .filter_map(|line| line.split_once(": "))
-        .map(|(val, terms)| {
-            (
-                val.parse().unwrap(),
-                terms
-                    .split_whitespace()
-                    .map(|t| t.parse().unwrap())
-                    .collect(),
-            )
-                .into()
+    lines(&input)
+        .filter(|line| !line.text.trim().is_empty())
+        .map(|line| {
+            let (val, terms) = line.split_once(": ")?;
+            Ok((val.parse()?, terms.words()?).into())
         })
-        .collect()
+        .collect::<Result<_, ParseError>>()
+        .unwrap()
 }
 
 pub fn part1(model: Model) -> Answer {
+    let mut perms = Perms::new(&Op::all_p1());
     model
         .into_iter()
-        .filter(|eq| eq.check(Op::all_p1().to_vec()))
+        .filter(|eq| eq.check(&perms.of_len(eq.terms.len() - 1)))
         .map(|eq| eq.val)
         .sum()
 }
 
 pub fn part2(model: Model) -> Answer {
+    let mut perms = Perms::new(&Op::all_p2());
     model
         .into_iter()
-        .filter(|eq| eq.check(Op::all_p2().to_vec()))
+        .filter(|eq| eq.check(&perms.of_len(eq.terms.len() - 1)))
         .map(|eq| eq.val)
         .sum()
 }
@@ -61,36 +61,44 @@ impl Op {
         match self {
             Op::Add => a + b,
             Op::Mul => a * b,
-            Op::Concat => a * (10u64.pow(b.ilog10() + 1)) + b,
+            Op::Concat => num::concat(a, b),
         }
     }
 }
 
-/// generate all n-length variations of ops
-#[cached]
-fn perms(n: usize, starting_ops: Vec<Op>) -> Vec<Vec<Op>> {
-    let mut ops = starting_ops.iter().cloned().map(|o| vec![o]).collect_vec();
+/// All the variations of one set of ops, remembered by length.
+struct Perms {
+    ops: Vec<Op>,
+    memo: Memo<usize, Rc<Vec<Vec<Op>>>, FxBuildHasher>,
+}
 
-    for _ in 1..n {
-        let new_ops: Vec<Vec<Op>> = ops
-            .drain(..)
-            .flat_map(|mut series| {
-                starting_ops
-                    .iter()
-                    .cloned()
-                    .map(|o| series.iter().cloned().chain([o].into_iter()).collect())
-                    .collect::<Vec<Vec<Op>>>()
-                // let mut a = series.clone();
-                // a.push(Op::Add);
-                // series.push(Op::Mul);
-                // vec![a, series]
-            })
-            .collect();
-
-        ops = new_ops;
+impl Perms {
+    fn new(ops: &[Op]) -> Self {
+        Self {
+            ops: ops.to_vec(),
+            memo: Memo::fast(),
+        }
     }
 
-    ops
+    /// generate all n-length variations of ops
+    fn of_len(&mut self, n: usize) -> Rc<Vec<Vec<Op>>> {
+        let ops = &self.ops;
+        self.memo.get(n, |perms, n| {
+            if n <= 1 {
+                return Rc::new(ops.iter().cloned().map(|o| vec![o]).collect());
+            }
+            Rc::new(
+                perms(n - 1)
+                    .iter()
+                    .flat_map(|series| {
+                        ops.iter()
+                            .cloned()
+                            .map(|o| series.iter().cloned().chain([o]).collect())
+                    })
+                    .collect(),
+            )
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -100,9 +108,7 @@ pub struct Eqn {
 }
 
 impl Eqn {
-    fn check(&self, starting_ops: Vec<Op>) -> bool {
-        let op_perms = perms(self.terms.len() - 1, starting_ops);
-
+    fn check(&self, op_perms: &[Vec<Op>]) -> bool {
         for ops in op_perms {
             let mut opsi = ops.iter();
             let out = self
@@ -141,13 +147,29 @@ mod tests {
         assert_eq!(Op::Concat.exec(1, 1), 11);
     }
 
+    #[test]
+    fn d7_perms_test() {
+        let mut p1 = Perms::new(&Op::all_p1());
+        let mut p2 = Perms::new(&Op::all_p2());
+        assert_eq!(p1.of_len(3).len(), 8);
+        assert_eq!(p2.of_len(3).len(), 27);
+        assert_eq!(p1.of_len(1).len(), 2);
+    }
+
     #[test]
     fn d7p2_problem_test() {
         assert!(Eqn {
             val: 7290,
             terms: vec![6, 8, 6, 15]
         }
-        .check(Op::all_p2().to_vec()));
+        .check(&Perms::new(&Op::all_p2()).of_len(3)));
+    }
+
+    #[test]
+    fn d7_blank_lines_test() {
+        let model = parse("\n190: 10 19\n\n3267: 81 40 27\n\n".to_string());
+        assert_eq!(model.len(), 2);
+        assert_eq!(model[1].terms, [81, 40, 27]);
     }
 
     #[test]
@@ -3,9 +3,9 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::point::Point;
+use crate::{geometry::primitive, point::Point, rect::Rect};
 
-type Model = (HashMap<char, Vec<Point<2>>>, Point<2>);
+type Model = (HashMap<char, Vec<Point<2>>>, Rect<2>);
 type Answer = usize;
 
 pub fn parse(input: String) -> Model {
@@ -18,18 +18,21 @@ pub fn parse(input: String) -> Model {
         for (x, c) in line.chars().enumerate() {
             if c != '.' {
                 map.entry(c)
-                    .and_modify(|points| points.push([x, y].into()))
-                    .or_insert_with(|| vec![[x, y].into()]);
+                    .and_modify(|points| points.push([x, y].try_into().unwrap()))
+                    .or_insert_with(|| vec![[x, y].try_into().unwrap()]);
             }
             width = x;
         }
         height = y
     }
 
-    (map, [width + 1, height + 1].into())
+    (
+        map,
+        Rect::from_size(Point::try_from([width + 1, height + 1]).unwrap()),
+    )
 }
 
-pub fn part1((map, size): Model) -> Answer {
+pub fn part1((map, bounds): Model) -> Answer {
     let mut antinodes: HashSet<Point<2>> = HashSet::new();
 
     for (ant, locs) in &map {
@@ -42,8 +45,7 @@ pub fn part1((map, size): Model) -> Answer {
                     let anti = *loc1 + dist;
                     // dbg!("PAIR", loc1, loc2, dist, anti);
 
-                    if anti.x() >= 0 && anti.y() >= 0 && anti.x() < size.x() && anti.y() < size.y()
-                    {
+                    if bounds.contains(&anti) {
                         antinodes.insert(anti);
                     }
                 }
@@ -54,7 +56,7 @@ pub fn part1((map, size): Model) -> Answer {
     antinodes.len()
 }
 
-pub fn part2((map, size): Model) -> Answer {
+pub fn part2((map, bounds): Model) -> Answer {
     let mut antinodes: HashSet<Point<2>> = HashSet::new();
 
     for (ant, locs) in &map {
@@ -66,22 +68,16 @@ pub fn part2((map, size): Model) -> Answer {
                     antinodes.insert(*loc1);
                     antinodes.insert(*loc2);
 
-                    // apply "lines"
+                    // apply "lines", stepping by the smallest lattice vector along the line so
+                    // no grid position on it is skipped
 
-                    let dist = *loc1 - *loc2;
-                    let mut anti = *loc1 + dist;
-                    loop {
-                        if anti.x() >= 0
-                            && anti.y() >= 0
-                            && anti.x() < size.x()
-                            && anti.y() < size.y()
-                        {
+                    let step = primitive(*loc1 - *loc2);
+                    for step in [step, -step] {
+                        let mut anti = *loc1;
+                        while bounds.contains(&anti) {
                             antinodes.insert(anti);
-                        } else {
-                            break;
+                            anti += step;
                         }
-
-                        anti = anti + dist;
                     }
                 }
             }
@@ -117,4 +113,11 @@ mod tests {
     fn d8p2_input_test() {
         assert_eq!(part2(parse(INPUT.to_string())), 1131);
     }
+
+    #[test]
+    fn d8p2_non_primitive_test() {
+        // the antennas are two steps apart, so the line also passes through the cells between
+        let map = "a.....\n......\n..a...\n......\n......\n......";
+        assert_eq!(part2(parse(map.to_string())), 6);
+    }
 }
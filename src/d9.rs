@@ -5,6 +5,8 @@ use std::collections::HashSet;
 
 use termion::clear;
 
+use crate::intervals::{IntervalSet, Span};
+
 type Model = Vec<u64>;
 type Answer = usize;
 
@@ -72,98 +74,43 @@ fn print_disk(disk: &[Block]) {
         .collect::<Vec<&str>>()
         .join("");
     println!("{}", msg);
-    std::thread::sleep_ms(4);
+    std::thread::sleep(std::time::Duration::from_millis(4));
 }
 
 pub fn part2(model: Model) -> Answer {
-    let mut disk: Vec<Block> = Vec::with_capacity(model.iter().sum::<u64>() as usize);
-    // map file ID to block index on disk
-    let mut file_locs: Vec<usize> = Vec::with_capacity(disk.len());
-    let mut last_id = 0;
-    let mut sizes: Vec<u64> = Vec::with_capacity(disk.len());
+    let (mut files, mut free) = spans(&model);
 
-    for (id, pair) in model.chunks(2).enumerate() {
-        file_locs.push(disk.len());
-        for _ in 0..pair[0] {
-            disk.push(Block::File(ID(id as u64)));
+    for file in files.iter_mut().rev() {
+        if let Some(space) = free.first_fit(file.len(), file.start) {
+            free.remove(space);
+            free.insert(*file);
+            *file = space;
         }
-        if pair.len() == 2 {
-            for _ in 0..pair[1] {
-                disk.push(Block::Empty);
-            }
-        }
-
-        // sizes[id] = pair[0];
-        sizes.push(pair[0]);
-        last_id = id;
     }
 
-    // print_disk(&disk);
-
-    for file_id in (0..=last_id).rev() {
-        let file_size = sizes[file_id];
-        // println!("\nmoving file: {file_id}");
-
-        if let Some(empty_idx) = find_space(&disk, file_size as usize) {
-            // println!("found empty space: {empty_idx}");
-            let file_idx = file_locs[file_id];
-            if empty_idx < file_idx {
-                for i in 0..(file_size as usize) {
-                    disk.swap(file_idx + i, empty_idx + i);
-                }
-            }
-        }
-        // print_disk(&disk);
-    }
-
-    // loop {}
-
-    // loop {
-
-    // start at the end of the disk
-    // get the ID of the file
-    // and iterate towards the front
-    // until a block != ID is found
-    //
-    // capture that length
-    //
-    // then start at the beginning and find the first Empty
-    // check its length, if not long enough then find the next one and check its length, repeat
-    // until a long enough Empty chain is found
-
-    // let block_id = fulls.pop().unwrap();
-    // let empty_id = empties.pop().unwrap();
-    // if empty_id >= total_file_size {
-    //     break;
-    // }
-    // disk.swap(block_id, empty_id);
-    // }
-
-    score_disk(&disk)
+    files
+        .iter()
+        .enumerate()
+        .map(|(id, file)| id * file.sum() as usize)
+        .sum()
 }
 
-/// Find the beginning of a series of Empty blocks that could fit a file of the given size, if any.
-fn find_space(disk: &[Block], size: usize) -> Option<usize> {
-    let mut space_so_far = 0;
-    let mut start = 0;
-
-    for (i, block) in disk.iter().enumerate() {
-        if let Block::Empty = block {
-            if space_so_far == 0 {
-                start = i;
-            }
-
-            space_so_far += 1;
-
-            if space_so_far == size {
-                return Some(start);
-            }
-        } else {
-            space_so_far = 0;
+/// Lay out the disk map as the span each file takes up, indexed by file ID, and the free space.
+fn spans(model: &[u64]) -> (Vec<Span>, IntervalSet) {
+    let mut files = Vec::with_capacity(model.len() / 2 + 1);
+    let mut free = IntervalSet::new();
+    let mut pos = 0;
+
+    for pair in model.chunks(2) {
+        files.push(Span::new(pos, pos + pair[0] as i64));
+        pos += pair[0] as i64;
+        if let Some(empty) = pair.get(1) {
+            free.insert(pos..pos + *empty as i64);
+            pos += *empty as i64;
         }
     }
 
-    None
+    (files, free)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -197,40 +144,13 @@ mod tests {
         assert_eq!(part2(parse(EXAMPLE.to_string())), 2858,);
     }
     #[test]
-    fn d9p2_find_space_test() {
-        use Block::*;
-        assert_eq!(
-            find_space(
-                &[
-                    File(ID(1)),
-                    File(ID(1)),
-                    Empty,
-                    File(ID(2)),
-                    Empty,
-                    Empty,
-                    Empty,
-                    File(ID(3))
-                ],
-                2
-            ),
-            Some(4)
-        );
-        assert_eq!(
-            find_space(
-                &[
-                    File(ID(1)),
-                    File(ID(1)),
-                    Empty,
-                    File(ID(2)),
-                    Empty,
-                    Empty,
-                    Empty,
-                    File(ID(3))
-                ],
-                4
-            ),
-            None
-        );
+    fn d9p2_spans_test() {
+        // 11.2...3
+        let (files, free) = spans(&[2, 1, 1, 3, 1]);
+        assert_eq!(files, [(0..2).into(), (3..4).into(), (7..8).into()]);
+        assert_eq!(free.first_fit(2, 7), Some((4..6).into()));
+        assert_eq!(free.first_fit(4, 7), None);
+        assert_eq!(free.first_fit(1, 3), Some((2..3).into()));
     }
 
     // #[test]
@@ -1,4 +1,6 @@
-use std::fmt::Display;
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::point::Point;
 
 /// The cardinal directions.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -10,6 +12,12 @@ pub enum CardDir {
 }
 
 impl CardDir {
+    /// All four directions, clockwise from Up.
+    pub fn all() -> [CardDir; 4] {
+        use CardDir::*;
+        [Up, Right, Down, Left]
+    }
+
     pub fn cw(&self) -> CardDir {
         use CardDir::*;
         match self {
@@ -19,20 +27,90 @@ impl CardDir {
             Left => Up,
         }
     }
+
+    pub fn ccw(&self) -> CardDir {
+        self.turn(-1)
+    }
+
+    pub fn opposite(&self) -> CardDir {
+        self.turn(2)
+    }
+
+    /// Turn by `n` quarter turns, clockwise for positive `n` and counter-clockwise for negative.
+    pub fn turn(&self, n: i32) -> CardDir {
+        let all = CardDir::all();
+        let i = all.iter().position(|d| d == self).unwrap() as i32;
+        all[(i + n).rem_euclid(4) as usize]
+    }
+
+    /// The unit step in the direction, with y increasing downwards.
+    pub fn delta(&self) -> Point<2> {
+        match self {
+            CardDir::Up => [0, -1],
+            CardDir::Down => [0, 1],
+            CardDir::Left => [-1, 0],
+            CardDir::Right => [1, 0],
+        }
+        .into()
+    }
 }
 
-impl From<char> for CardDir {
-    fn from(value: char) -> Self {
-        match value {
-            'v' => CardDir::Down,
-            '>' => CardDir::Right,
-            '<' => CardDir::Left,
-            '^' => CardDir::Up,
-            _ => panic!("char {value} is not a valid direction"),
+/// A char or string that doesn't name a direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirError(pub String);
+
+impl Display for ParseDirError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a valid direction", self.0)
+    }
+}
+
+impl Error for ParseDirError {}
+
+/// Parse an arrow (`^v<>`), or the initial of a relative (`UDLR`) or compass (`NESW`) direction
+/// in either case.
+impl TryFrom<char> for CardDir {
+    type Error = ParseDirError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value.to_ascii_uppercase() {
+            '^' | 'U' | 'N' => Ok(CardDir::Up),
+            'V' | 'D' | 'S' => Ok(CardDir::Down),
+            '<' | 'L' | 'W' => Ok(CardDir::Left),
+            '>' | 'R' | 'E' => Ok(CardDir::Right),
+            _ => Err(ParseDirError(value.to_string())),
         }
     }
 }
 
+/// Parse a word like `up` or `north` in any case, or any single char accepted by
+/// `CardDir::try_from`.
+impl FromStr for CardDir {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return CardDir::try_from(c).map_err(|_| ParseDirError(s.to_string()));
+        }
+
+        match s.to_ascii_lowercase().as_str() {
+            "up" | "north" => Ok(CardDir::Up),
+            "down" | "south" => Ok(CardDir::Down),
+            "left" | "west" => Ok(CardDir::Left),
+            "right" | "east" => Ok(CardDir::Right),
+            _ => Err(ParseDirError(s.to_string())),
+        }
+    }
+}
+
+/// The unit step in the direction, with y increasing downwards.
+impl From<CardDir> for Point<2> {
+    fn from(dir: CardDir) -> Self {
+        dir.delta()
+    }
+}
+
 impl Display for CardDir {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -49,7 +127,7 @@ impl Display for CardDir {
 }
 
 /// Cardinal and ordinal (intercardinal) directions.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum CardOrdDir {
     UpLeft,
     Up,
@@ -80,4 +158,123 @@ impl CardOrdDir {
             Left => UpLeft,
         }
     }
+
+    /// The unit step in the direction, with y increasing downwards.
+    pub fn delta(&self) -> Point<2> {
+        use CardOrdDir::*;
+        match self {
+            UpLeft => [-1, -1],
+            Up => [0, -1],
+            UpRight => [1, -1],
+            Left => [-1, 0],
+            Right => [1, 0],
+            DownLeft => [-1, 1],
+            Down => [0, 1],
+            DownRight => [1, 1],
+        }
+        .into()
+    }
+}
+
+/// The unit step in the direction, with y increasing downwards.
+impl From<CardOrdDir> for Point<2> {
+    fn from(dir: CardOrdDir) -> Self {
+        dir.delta()
+    }
+}
+
+/// A position and the direction it's facing, eg a guard or a robot moving around a grid.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Heading {
+    pub pos: Point<2>,
+    pub dir: CardDir,
+}
+
+impl Heading {
+    pub fn new(pos: impl Into<Point<2>>, dir: CardDir) -> Self {
+        Self {
+            pos: pos.into(),
+            dir,
+        }
+    }
+
+    /// The heading after moving one step forward.
+    pub fn step(&self) -> Heading {
+        Heading::new(self.pos + self.dir.delta(), self.dir)
+    }
+
+    /// The heading after turning 90° counter-clockwise on the spot.
+    pub fn turn_left(&self) -> Heading {
+        Heading::new(self.pos, self.dir.ccw())
+    }
+
+    /// The heading after turning 90° clockwise on the spot.
+    pub fn turn_right(&self) -> Heading {
+        Heading::new(self.pos, self.dir.cw())
+    }
+}
+
+#[cfg(test)]
+mod direction_tests {
+    use super::*;
+
+    #[test]
+    fn turn_test() {
+        for dir in CardDir::all() {
+            assert_eq!(dir.cw().ccw(), dir);
+            assert_eq!(dir.turn(1), dir.cw());
+            assert_eq!(dir.turn(-1), dir.ccw());
+            assert_eq!(dir.turn(4), dir);
+            assert_eq!(dir.turn(-6), dir.opposite());
+            assert_eq!(dir.opposite().delta(), -dir.delta());
+        }
+        assert_eq!(CardDir::Up.ccw(), CardDir::Left);
+        assert_eq!(CardDir::Left.opposite(), CardDir::Right);
+    }
+
+    #[test]
+    fn delta_test() {
+        for dir in CardDir::all() {
+            assert_eq!(dir.delta().manhattan(&[0, 0].into()), 1);
+        }
+        for dir in CardOrdDir::all() {
+            assert_eq!(dir.delta().chebyshev(&[0, 0].into()), 1);
+            assert_eq!(dir.cw().cw().cw().cw().delta(), -dir.delta());
+        }
+        assert_eq!(CardDir::Up.delta(), [0, -1].into());
+        assert_eq!(CardOrdDir::DownLeft.delta(), [-1, 1].into());
+    }
+
+    #[test]
+    fn parse_test() {
+        for (dir, chars) in [
+            (CardDir::Up, "^UuNn"),
+            (CardDir::Down, "vDdSs"),
+            (CardDir::Left, "<LlWw"),
+            (CardDir::Right, ">RrEe"),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(CardDir::try_from(c), Ok(dir));
+                assert_eq!(c.to_string().parse(), Ok(dir));
+            }
+        }
+
+        assert_eq!("north".parse(), Ok(CardDir::Up));
+        assert_eq!("Down".parse(), Ok(CardDir::Down));
+        assert_eq!("WEST".parse(), Ok(CardDir::Left));
+        assert_eq!("right".parse(), Ok(CardDir::Right));
+
+        assert_eq!(CardDir::try_from('x'), Err(ParseDirError("x".to_string())));
+        assert!("".parse::<CardDir>().is_err());
+        assert!("upward".parse::<CardDir>().is_err());
+    }
+
+    #[test]
+    fn heading_test() {
+        let h = Heading::new([2, 2], CardDir::Up);
+        assert_eq!(h.step(), Heading::new([2, 1], CardDir::Up));
+        assert_eq!(h.turn_right().step(), Heading::new([3, 2], CardDir::Right));
+        assert_eq!(h.turn_left().step(), Heading::new([1, 2], CardDir::Left));
+        assert_eq!(h.turn_left().turn_left(), h.turn_right().turn_right());
+    }
 }
@@ -0,0 +1,241 @@
+use crate::{num::gcd, point::Point};
+
+/// The z component of the cross product of two vectors.  Positive when `b` is counter-clockwise
+/// from `a` with y pointing up (clockwise on screen, where y points down).
+pub fn cross(a: Point<2>, b: Point<2>) -> i64 {
+    a.x() * b.y() - a.y() * b.x()
+}
+
+/// The shortest lattice vector pointing the same way as `v`, ie `v` divided by the gcd of its
+/// coordinates.  The zero vector stays zero.
+pub fn primitive(v: Point<2>) -> Point<2> {
+    match gcd(v.x(), v.y()) {
+        0 => v,
+        n => v / n,
+    }
+}
+
+/// Every lattice point on the segment from `a` to `b`, in order, including both ends.
+pub fn lattice_points(a: Point<2>, b: Point<2>) -> impl Iterator<Item = Point<2>> {
+    let d = b - a;
+    let n = gcd(d.x(), d.y());
+    let step = primitive(d);
+    std::iter::successors(Some(a), move |p| Some(*p + step)).take(n as usize + 1)
+}
+
+/// Whether `p` lies on the segment from `a` to `b`, including its ends.
+pub fn on_segment(p: Point<2>, a: Point<2>, b: Point<2>) -> bool {
+    cross(b - a, p - a) == 0
+        && (a.x().min(b.x())..=a.x().max(b.x())).contains(&p.x())
+        && (a.y().min(b.y())..=a.y().max(b.y())).contains(&p.y())
+}
+
+/// Where two segments meet.  See [segment_intersection].
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Intersection {
+    /// The segments don't touch.
+    None,
+    /// The segments meet at the single point `num / den`, in lowest terms with `den` positive.
+    /// It's a lattice point exactly when `den` is 1.
+    Point { num: Point<2>, den: i64 },
+    /// The segments are collinear and share the segment between these two distinct points.
+    Overlap(Point<2>, Point<2>),
+}
+
+/// Find where the segment from `a1` to `a2` meets the segment from `b1` to `b2`, including
+/// their ends.  Segments may be a single point.
+pub fn segment_intersection(
+    a1: Point<2>,
+    a2: Point<2>,
+    b1: Point<2>,
+    b2: Point<2>,
+) -> Intersection {
+    let r = a2 - a1;
+    let s = b2 - b1;
+    let q = b1 - a1;
+    let rxs = cross(r, s);
+
+    if rxs == 0 {
+        // Parallel, collinear or degenerate.  Any shared stretch runs between ends of the
+        // segments, so find the ends that lie on both and take the outermost pair.
+        let along = if r == Point::new(&[0, 0]) { s } else { r };
+        let shared = [a1, a2, b1, b2]
+            .into_iter()
+            .filter(|p| on_segment(*p, a1, a2) && on_segment(*p, b1, b2));
+        let first = shared.clone().min_by_key(|p| (*p - a1).dot(&along));
+        let last = shared.max_by_key(|p| (*p - a1).dot(&along));
+        return match (first, last) {
+            (Some(first), Some(last)) if first != last => Intersection::Overlap(first, last),
+            (Some(p), _) => Intersection::Point { num: p, den: 1 },
+            _ => Intersection::None,
+        };
+    }
+
+    // The crossing is at a1 + r * t and b1 + s * u, with t = tn / den and u = un / den.
+    let sign = rxs.signum();
+    let den = rxs.abs();
+    let tn = cross(q, s) * sign;
+    let un = cross(q, r) * sign;
+    if !(0..=den).contains(&tn) || !(0..=den).contains(&un) {
+        return Intersection::None;
+    }
+
+    let num = a1 * den + r * tn;
+    let n = gcd(gcd(num.x(), num.y()), den);
+    Intersection::Point {
+        num: num / n,
+        den: den / n,
+    }
+}
+
+/// Twice the signed area of a simple polygon, by the shoelace formula.  Positive when the
+/// vertices run counter-clockwise with y pointing up (clockwise on screen, where y points down).
+/// Doubling keeps the result an exact integer.
+pub fn shoelace(vertices: &[Point<2>]) -> i64 {
+    (0..vertices.len())
+        .map(|i| cross(vertices[i], vertices[(i + 1) % vertices.len()]))
+        .sum()
+}
+
+/// The number of lattice points on the boundary of a polygon.
+pub fn boundary_points(vertices: &[Point<2>]) -> i64 {
+    (0..vertices.len())
+        .map(|i| {
+            let d = vertices[(i + 1) % vertices.len()] - vertices[i];
+            gcd(d.x(), d.y())
+        })
+        .sum()
+}
+
+/// The number of lattice points strictly inside a simple polygon whose vertices are lattice
+/// points, by Pick's theorem: A = I + B/2 - 1.
+pub fn interior_points(vertices: &[Point<2>]) -> i64 {
+    (shoelace(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+#[cfg(test)]
+mod geometry_tests {
+    use super::*;
+
+    fn p(x: i64, y: i64) -> Point<2> {
+        Point::new(&[x, y])
+    }
+
+    #[test]
+    fn primitive_test() {
+        assert_eq!(primitive(p(4, -6)), p(2, -3));
+        assert_eq!(primitive(p(0, 5)), p(0, 1));
+        assert_eq!(primitive(p(-7, 0)), p(-1, 0));
+        assert_eq!(primitive(p(3, 5)), p(3, 5));
+        assert_eq!(primitive(p(0, 0)), p(0, 0));
+    }
+
+    #[test]
+    fn lattice_points_test() {
+        assert_eq!(
+            lattice_points(p(0, 0), p(6, 3)).collect::<Vec<_>>(),
+            vec![p(0, 0), p(2, 1), p(4, 2), p(6, 3)]
+        );
+        assert_eq!(
+            lattice_points(p(1, 1), p(2, 4)).collect::<Vec<_>>(),
+            vec![p(1, 1), p(2, 4)]
+        );
+        assert_eq!(
+            lattice_points(p(3, 3), p(3, 3)).collect::<Vec<_>>(),
+            vec![p(3, 3)]
+        );
+        assert_eq!(lattice_points(p(5, -5), p(-5, 5)).count(), 11);
+    }
+
+    #[test]
+    fn segment_intersection_test() {
+        // a lattice crossing
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 4), p(0, 4), p(4, 0)),
+            Intersection::Point {
+                num: p(2, 2),
+                den: 1
+            }
+        );
+        // a crossing between lattice points, at (1/2, 1/2)
+        assert_eq!(
+            segment_intersection(p(0, 0), p(1, 1), p(0, 1), p(1, 0)),
+            Intersection::Point {
+                num: p(1, 1),
+                den: 2
+            }
+        );
+        // touching at an end
+        assert_eq!(
+            segment_intersection(p(0, 0), p(2, 0), p(2, 0), p(2, 5)),
+            Intersection::Point {
+                num: p(2, 0),
+                den: 1
+            }
+        );
+        // lines cross, but beyond the end of a segment
+        assert_eq!(
+            segment_intersection(p(0, 0), p(1, 1), p(0, 4), p(4, 0)),
+            Intersection::None
+        );
+        // parallel
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 0), p(0, 1), p(4, 1)),
+            Intersection::None
+        );
+        // collinear and overlapping, in either direction
+        assert_eq!(
+            segment_intersection(p(0, 0), p(6, 3), p(8, 4), p(2, 1)),
+            Intersection::Overlap(p(2, 1), p(6, 3))
+        );
+        // collinear and touching end to end
+        assert_eq!(
+            segment_intersection(p(0, 0), p(2, 2), p(2, 2), p(5, 5)),
+            Intersection::Point {
+                num: p(2, 2),
+                den: 1
+            }
+        );
+        // collinear with a gap
+        assert_eq!(
+            segment_intersection(p(0, 0), p(1, 0), p(3, 0), p(5, 0)),
+            Intersection::None
+        );
+        // a single point on a segment
+        assert_eq!(
+            segment_intersection(p(1, 2), p(1, 2), p(0, 0), p(2, 4)),
+            Intersection::Point {
+                num: p(1, 2),
+                den: 1
+            }
+        );
+    }
+
+    #[test]
+    fn shoelace_test() {
+        let square = [p(0, 0), p(4, 0), p(4, 4), p(0, 4)];
+        assert_eq!(shoelace(&square), 32);
+        let reversed: Vec<Point<2>> = square.iter().rev().copied().collect();
+        assert_eq!(shoelace(&reversed), -32);
+        assert_eq!(shoelace(&[p(0, 0), p(3, 0), p(0, 1)]), 3);
+    }
+
+    #[test]
+    fn pick_test() {
+        // a 4x4 square has 3x3 interior points and 16 on its boundary
+        let square = [p(0, 0), p(4, 0), p(4, 4), p(0, 4)];
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+
+        // an L shape, with its boundary checked by brute force
+        let l = [p(0, 0), p(6, 0), p(6, 2), p(2, 2), p(2, 5), p(0, 5)];
+        assert_eq!(shoelace(&l).abs(), 2 * (12 + 6));
+        let boundary = (0..=6)
+            .flat_map(|x| (0..=5).map(move |y| p(x, y)))
+            .filter(|q| (0..l.len()).any(|i| on_segment(*q, l[i], l[(i + 1) % l.len()])))
+            .count();
+        assert_eq!(boundary_points(&l), boundary as i64);
+        // interior: (1..=5, 1) along the foot and (1, 2..=4) up the leg
+        assert_eq!(interior_points(&l), 5 + 3);
+    }
+}
@@ -0,0 +1,580 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A graph stored as adjacency lists, with nodes of type `N` and edge weights of type `E`.
+/// Nodes keep the order they were added in, and algorithms visit neighbors in the order their
+/// edges were added, so results are deterministic.
+#[derive(Debug, Clone)]
+pub struct Graph<N, E = ()> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, E)>>,
+    directed: bool,
+}
+
+impl<N: Clone + Eq + Hash, E: Clone> Graph<N, E> {
+    pub fn new(directed: bool) -> Self {
+        Self {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            edges: Vec::new(),
+            directed,
+        }
+    }
+
+    /// A directed graph with the given weighted edges, from the first node to the second.
+    pub fn directed(edges: impl IntoIterator<Item = (N, N, E)>) -> Self {
+        let mut g = Self::new(true);
+        for (a, b, e) in edges {
+            g.add_edge(a, b, e);
+        }
+        g
+    }
+
+    /// An undirected graph with the given weighted edges.
+    pub fn undirected(edges: impl IntoIterator<Item = (N, N, E)>) -> Self {
+        let mut g = Self::new(false);
+        for (a, b, e) in edges {
+            g.add_edge(a, b, e);
+        }
+        g
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Add a node if it isn't already in the graph.  Returns its index.
+    pub fn add_node(&mut self, n: N) -> usize {
+        if let Some(&i) = self.index.get(&n) {
+            return i;
+        }
+        self.nodes.push(n.clone());
+        self.edges.push(Vec::new());
+        self.index.insert(n, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    /// Add an edge, and its nodes if they're new.  In an undirected graph the edge goes both
+    /// ways.
+    pub fn add_edge(&mut self, a: N, b: N, e: E) {
+        let a = self.add_node(a);
+        let b = self.add_node(b);
+        if !self.directed && a != b {
+            self.edges[b].push((a, e.clone()));
+        }
+        self.edges[a].push((b, e));
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    pub fn contains(&self, n: &N) -> bool {
+        self.index.contains_key(n)
+    }
+
+    /// The nodes reachable by one edge from `n`, with the edge weights.  Empty if `n` isn't in
+    /// the graph.
+    pub fn neighbors(&self, n: &N) -> impl Iterator<Item = (&N, &E)> {
+        self.index
+            .get(n)
+            .into_iter()
+            .flat_map(|&i| self.edges[i].iter())
+            .map(|(j, e)| (&self.nodes[*j], e))
+    }
+
+    pub fn has_edge(&self, a: &N, b: &N) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&a), Some(&b)) => self.edges[a].iter().any(|(j, _)| *j == b),
+            _ => false,
+        }
+    }
+
+    /// Nodes reachable from `start` in breadth-first order, with the number of edges to reach
+    /// each one.
+    pub fn bfs(&self, start: &N) -> Vec<(N, usize)> {
+        let Some(&start) = self.index.get(start) else {
+            return vec![];
+        };
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut order = Vec::new();
+        seen[start] = true;
+
+        while let Some((i, hops)) = queue.pop_front() {
+            order.push((self.nodes[i].clone(), hops));
+            for (j, _) in &self.edges[i] {
+                if !seen[*j] {
+                    seen[*j] = true;
+                    queue.push_back((*j, hops + 1));
+                }
+            }
+        }
+        order
+    }
+
+    /// Nodes reachable from `start` in depth-first preorder.
+    pub fn dfs(&self, start: &N) -> Vec<N> {
+        let Some(&start) = self.index.get(start) else {
+            return vec![];
+        };
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        let mut order = Vec::new();
+
+        while let Some(i) = stack.pop() {
+            if seen[i] {
+                continue;
+            }
+            seen[i] = true;
+            order.push(self.nodes[i].clone());
+            // push in reverse so the first neighbor is visited first
+            stack.extend(self.edges[i].iter().rev().map(|(j, _)| *j));
+        }
+        order
+    }
+
+    /// Order the nodes so every edge points forwards, by Kahn's algorithm.  If that's
+    /// impossible, returns one of the cycles that prevents it, with each node followed by the
+    /// node its edge leads to (and the last leading back to the first).
+    ///
+    /// Panics if the graph is undirected, where every edge would be a cycle.
+    pub fn topological_sort(&self) -> Result<Vec<N>, Vec<N>> {
+        assert!(self.directed, "can't sort an undirected graph");
+        let mut in_degree = vec![0; self.len()];
+        for edges in &self.edges {
+            for (j, _) in edges {
+                in_degree[*j] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<usize> = (0..self.len()).filter(|i| in_degree[*i] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(i) = queue.pop_front() {
+            order.push(i);
+            for (j, _) in &self.edges[i] {
+                in_degree[*j] -= 1;
+                if in_degree[*j] == 0 {
+                    queue.push_back(*j);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            return Ok(order.into_iter().map(|i| self.nodes[i].clone()).collect());
+        }
+
+        // Every node left over has an edge coming in from another left over node, so walking
+        // those edges backwards must eventually go around a cycle.
+        let mut preds = vec![None; self.len()];
+        for (i, edges) in self.edges.iter().enumerate() {
+            for (j, _) in edges {
+                if in_degree[i] > 0 && in_degree[*j] > 0 {
+                    preds[*j].get_or_insert(i);
+                }
+            }
+        }
+        let mut walked = vec![false; self.len()];
+        let mut i = (0..self.len()).find(|i| in_degree[*i] > 0).unwrap();
+        while !walked[i] {
+            walked[i] = true;
+            i = preds[i].unwrap();
+        }
+        let mut cycle = vec![i];
+        let mut j = preds[i].unwrap();
+        while j != i {
+            cycle.push(j);
+            j = preds[j].unwrap();
+        }
+        cycle.reverse();
+        Err(cycle.into_iter().map(|i| self.nodes[i].clone()).collect())
+    }
+
+    /// The strongly connected components, by Tarjan's algorithm.  Each component is a set of
+    /// nodes that can all reach each other.  Components come out in reverse topological order:
+    /// no edge leads from a component to a later one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        struct Tarjan<'a, N, E> {
+            graph: &'a Graph<N, E>,
+            next_index: usize,
+            index: Vec<Option<usize>>,
+            low_link: Vec<usize>,
+            on_stack: Vec<bool>,
+            stack: Vec<usize>,
+            components: Vec<Vec<usize>>,
+        }
+
+        impl<N, E> Tarjan<'_, N, E> {
+            /// Give a node its index and put it on the stack.
+            fn open(&mut self, i: usize) {
+                self.index[i] = Some(self.next_index);
+                self.low_link[i] = self.next_index;
+                self.next_index += 1;
+                self.stack.push(i);
+                self.on_stack[i] = true;
+            }
+
+            /// Visit every node reachable from `root`.  The path being explored is kept on an
+            /// explicit stack, with the next edge to follow from each node, so a long path
+            /// can't overflow the call stack.
+            fn visit(&mut self, root: usize) {
+                self.open(root);
+                let mut path = vec![(root, 0)];
+                while let Some((i, e)) = path.last_mut() {
+                    let i = *i;
+                    if let Some((j, _)) = self.graph.edges[i].get(*e) {
+                        let j = *j;
+                        *e += 1;
+                        match self.index[j] {
+                            None => {
+                                self.open(j);
+                                path.push((j, 0));
+                            }
+                            Some(index) if self.on_stack[j] => {
+                                self.low_link[i] = self.low_link[i].min(index);
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // every edge from i has been followed
+                    path.pop();
+                    if let Some((parent, _)) = path.last() {
+                        self.low_link[*parent] = self.low_link[*parent].min(self.low_link[i]);
+                    }
+                    if Some(self.low_link[i]) == self.index[i] {
+                        let mut component = Vec::new();
+                        loop {
+                            let j = self.stack.pop().unwrap();
+                            self.on_stack[j] = false;
+                            component.push(j);
+                            if j == i {
+                                break;
+                            }
+                        }
+                        component.reverse();
+                        self.components.push(component);
+                    }
+                }
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            graph: self,
+            next_index: 0,
+            index: vec![None; self.len()],
+            low_link: vec![0; self.len()],
+            on_stack: vec![false; self.len()],
+            stack: Vec::new(),
+            components: Vec::new(),
+        };
+        for i in 0..self.len() {
+            if tarjan.index[i].is_none() {
+                tarjan.visit(i);
+            }
+        }
+
+        tarjan
+            .components
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| self.nodes[i].clone()).collect())
+            .collect()
+    }
+
+    /// Groups of nodes joined by edges, ignoring which way the edges point.
+    pub fn connected_components(&self) -> Vec<Vec<N>> {
+        // union-find, joining the ends of every edge
+        let mut parent: Vec<usize> = (0..self.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        for (i, edges) in self.edges.iter().enumerate() {
+            for (j, _) in edges {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, *j));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut components: Vec<Vec<N>> = Vec::new();
+        let mut component_of = HashMap::new();
+        for i in 0..self.len() {
+            let r = root(&mut parent, i);
+            let c = *component_of.entry(r).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[c].push(self.nodes[i].clone());
+        }
+        components
+    }
+
+    /// The graph made of just the given nodes, and the edges between them.
+    pub fn subgraph<'a>(&self, nodes: impl IntoIterator<Item = &'a N>) -> Graph<N, E>
+    where
+        N: 'a,
+    {
+        let keep: HashSet<usize> = nodes
+            .into_iter()
+            .filter_map(|n| self.index.get(n).copied())
+            .collect();
+
+        let mut g = Graph::new(self.directed);
+        for i in (0..self.len()).filter(|i| keep.contains(i)) {
+            g.add_node(self.nodes[i].clone());
+        }
+        for i in (0..self.len()).filter(|i| keep.contains(i)) {
+            let a = g.index[&self.nodes[i]];
+            for (j, e) in self.edges[i].iter().filter(|(j, _)| keep.contains(j)) {
+                // undirected edges are already stored both ways, so copy them as they are
+                g.edges[a].push((g.index[&self.nodes[*j]], e.clone()));
+            }
+        }
+        g
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    /// A directed, unweighted graph with edges from the first node of each pair to the second.
+    pub fn directed_pairs(edges: impl IntoIterator<Item = (N, N)>) -> Self {
+        Self::directed(edges.into_iter().map(|(a, b)| (a, b, ())))
+    }
+
+    /// An undirected, unweighted graph with an edge joining each pair.
+    pub fn undirected_pairs(edges: impl IntoIterator<Item = (N, N)>) -> Self {
+        Self::undirected(edges.into_iter().map(|(a, b)| (a, b, ())))
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Clone + Eq + Hash,
+    E: Copy + Ord + Default + Add<Output = E>,
+{
+    /// The total weight of the lightest path from `start` to each reachable node, by Dijkstra's
+    /// algorithm.  Weights must not be negative.  `E::default()` is the weight of an empty path.
+    pub fn dijkstra(&self, start: &N) -> HashMap<N, E> {
+        let (dist, _) = self.dijkstra_from(start);
+        dist.into_iter()
+            .enumerate()
+            .filter_map(|(i, d)| Some((self.nodes[i].clone(), d?)))
+            .collect()
+    }
+
+    /// The lightest path from `start` to `goal`, with its total weight, or None if `goal` can't
+    /// be reached.
+    pub fn shortest_path(&self, start: &N, goal: &N) -> Option<(E, Vec<N>)> {
+        let (dist, prev) = self.dijkstra_from(start);
+        let mut i = *self.index.get(goal)?;
+        let total = dist[i]?;
+        let mut path = vec![self.nodes[i].clone()];
+        while let Some(p) = prev[i] {
+            path.push(self.nodes[p].clone());
+            i = p;
+        }
+        path.reverse();
+        Some((total, path))
+    }
+
+    /// The lightest distance to each node by index, and the node before it on that path.
+    #[allow(clippy::type_complexity)]
+    fn dijkstra_from(&self, start: &N) -> (Vec<Option<E>>, Vec<Option<usize>>) {
+        let mut dist = vec![None; self.len()];
+        let mut prev = vec![None; self.len()];
+        let Some(&start) = self.index.get(start) else {
+            return (dist, prev);
+        };
+
+        let mut heap = BinaryHeap::from([Reverse((E::default(), start))]);
+        dist[start] = Some(E::default());
+        while let Some(Reverse((d, i))) = heap.pop() {
+            if dist[i].is_some_and(|best| d > best) {
+                continue;
+            }
+            for (j, e) in &self.edges[i] {
+                let next = d + *e;
+                if dist[*j].is_none_or(|best| next < best) {
+                    dist[*j] = Some(next);
+                    prev[*j] = Some(i);
+                    heap.push(Reverse((next, *j)));
+                }
+            }
+        }
+        (dist, prev)
+    }
+}
+
+#[cfg(test)]
+mod graph_tests {
+    use super::*;
+
+    /// A small directed graph: a diamond from 1 to 4, then a tail to 5, and 6 on its own.
+    fn diamond() -> Graph<u32> {
+        let mut g = Graph::directed_pairs([(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]);
+        g.add_node(6);
+        g
+    }
+
+    #[test]
+    fn build_test() {
+        let g = diamond();
+        assert_eq!(g.len(), 6);
+        assert!(g.has_edge(&1, &2));
+        assert!(!g.has_edge(&2, &1));
+        assert!(!g.has_edge(&1, &9));
+        assert_eq!(g.neighbors(&1).map(|(n, _)| *n).collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(g.neighbors(&9).count(), 0);
+
+        let u = Graph::undirected_pairs([("a", "b"), ("b", "c")]);
+        assert!(u.has_edge(&"b", &"a"));
+        assert_eq!(u.neighbors(&"b").count(), 2);
+    }
+
+    #[test]
+    fn traversal_test() {
+        let g = diamond();
+        assert_eq!(g.bfs(&1), vec![(1, 0), (2, 1), (3, 1), (4, 2), (5, 3)]);
+        assert_eq!(g.dfs(&1), vec![1, 2, 4, 5, 3]);
+        assert_eq!(g.bfs(&6), vec![(6, 0)]);
+        assert!(g.dfs(&9).is_empty());
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        let g = Graph::undirected([
+            ('a', 'b', 7),
+            ('a', 'c', 9),
+            ('a', 'f', 14),
+            ('b', 'c', 10),
+            ('b', 'd', 15),
+            ('c', 'd', 11),
+            ('c', 'f', 2),
+            ('d', 'e', 6),
+            ('e', 'f', 9),
+            ('x', 'y', 1),
+        ]);
+        let dist = g.dijkstra(&'a');
+        assert_eq!(dist[&'e'], 20);
+        assert_eq!(dist[&'d'], 20);
+        assert_eq!(dist[&'a'], 0);
+        assert!(!dist.contains_key(&'x'));
+
+        assert_eq!(
+            g.shortest_path(&'a', &'e'),
+            Some((20, vec!['a', 'c', 'f', 'e']))
+        );
+        assert_eq!(g.shortest_path(&'a', &'a'), Some((0, vec!['a'])));
+        assert_eq!(g.shortest_path(&'a', &'y'), None);
+    }
+
+    #[test]
+    fn topological_sort_test() {
+        let g = diamond();
+        let order = g.topological_sort().unwrap();
+        assert_eq!(order, vec![1, 6, 2, 3, 4, 5]);
+        let pos = |n| order.iter().position(|m| *m == n).unwrap();
+        for a in g.nodes() {
+            for (b, _) in g.neighbors(a) {
+                assert!(pos(*a) < pos(*b));
+            }
+        }
+
+        // 2 -> 3 -> 4 -> 2 is a cycle, reachable from 1 and leading on to 5
+        let g = Graph::directed_pairs([(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)]);
+        let cycle = g.topological_sort().unwrap_err();
+        assert_eq!(cycle.len(), 3);
+        for (i, n) in cycle.iter().enumerate() {
+            assert!(g.has_edge(n, &cycle[(i + 1) % cycle.len()]));
+        }
+
+        let g = Graph::directed_pairs([(1, 1)]);
+        assert_eq!(g.topological_sort(), Err(vec![1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "undirected")]
+    fn topological_sort_undirected_test() {
+        let _ = Graph::undirected_pairs([(1, 2)]).topological_sort();
+    }
+
+    #[test]
+    fn scc_test() {
+        let g = Graph::directed_pairs([(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4), (5, 6)]);
+        let mut sccs = g.strongly_connected_components();
+        assert_eq!(sccs.len(), 3);
+        // reverse topological order: the sink comes first
+        assert_eq!(sccs[0], vec![6]);
+        for scc in sccs.iter_mut() {
+            scc.sort();
+        }
+        assert_eq!(sccs, vec![vec![6], vec![4, 5], vec![1, 2, 3]]);
+
+        // an acyclic graph has only single-node components
+        assert!(diamond()
+            .strongly_connected_components()
+            .iter()
+            .all(|c| c.len() == 1));
+    }
+
+    #[test]
+    fn scc_long_chain_test() {
+        // far deeper than the call stack would allow if each node were a recursive call
+        let g = Graph::directed_pairs((0..200_000).map(|i| (i, i + 1)));
+        let sccs = g.strongly_connected_components();
+        assert_eq!(sccs.len(), 200_001);
+        assert_eq!(sccs[0], vec![200_000]);
+
+        // closing the chain makes it one component
+        let g = Graph::directed_pairs((0..200_000).map(|i| (i, (i + 1) % 200_000)));
+        let sccs = g.strongly_connected_components();
+        assert_eq!(sccs.len(), 1);
+        assert_eq!(sccs[0].len(), 200_000);
+    }
+
+    #[test]
+    fn connected_components_test() {
+        let g = Graph::undirected_pairs([(1, 2), (3, 4), (2, 5), (6, 6)]);
+        assert_eq!(
+            g.connected_components(),
+            vec![vec![1, 2, 5], vec![3, 4], vec![6]]
+        );
+        // direction is ignored
+        assert_eq!(
+            diamond().connected_components(),
+            vec![vec![1, 2, 3, 4, 5], vec![6]]
+        );
+    }
+
+    #[test]
+    fn subgraph_test() {
+        let g = diamond();
+        let sub = g.subgraph(&[4, 2, 1, 9]);
+        assert_eq!(sub.nodes().copied().collect::<Vec<_>>(), [1, 2, 4]);
+        assert!(sub.has_edge(&1, &2));
+        assert!(sub.has_edge(&2, &4));
+        assert!(!sub.contains(&3));
+        assert_eq!(sub.topological_sort(), Ok(vec![1, 2, 4]));
+
+        let u = Graph::undirected_pairs([(1, 2), (2, 3)]);
+        let sub = u.subgraph(&[2, 3]);
+        assert!(sub.has_edge(&3, &2));
+        assert_eq!(sub.neighbors(&2).count(), 1);
+    }
+}
@@ -1,6 +1,17 @@
-use std::fmt::Display;
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    io::{self, Write},
+};
 
-use crate::point::Point;
+use termion::{color, style};
+
+use crate::{
+    direction::{CardDir, CardOrdDir},
+    geometry::lattice_points,
+    point::Point,
+    rect::Rect,
+};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Grid<T: Copy> {
@@ -33,15 +44,13 @@ impl<T: Copy> Grid<T> {
     }
 
     pub fn getp(&self, p: Point<2>) -> Option<T> {
-        self.cells
-            .get(p.y() as usize)
-            .and_then(|row| row.get(p.x() as usize))
-            .copied()
+        let [x, y] = <[usize; 2]>::try_from(p).ok()?;
+        self.get(x, y)
     }
 
     pub fn cols(&self) -> Vec<Vec<T>> {
-        (0..self.cells[0].len())
-            .map(|x| (0..self.cells.len()).map(|y| self.cells[y][x]).collect())
+        (0..self.width())
+            .map(|x| (0..self.height()).map(|y| self.cells[y][x]).collect())
             .collect()
     }
 
@@ -72,11 +81,122 @@ impl<T: Copy> Grid<T> {
 
     /// Set data in the grid using a Point as coordinates.  Panics if the coordinates are out of bounds.
     pub fn setp(&mut self, p: Point<2>, new_data: T) {
-        assert!(p.x() > 0);
-        assert!(p.y() > 0);
-        assert!((p.y() as usize) < self.cells.len());
-        assert!((p.x() as usize) < self.cells[0].len());
-        self.cells[p.y() as usize][p.x() as usize] = new_data;
+        let [x, y] = <[usize; 2]>::try_from(p).expect("negative grid coordinates");
+        self.set(x, y, new_data);
+    }
+
+    /// Return a copy of the grid rotated 90° clockwise.  The first column (read bottom to top)
+    /// becomes the first row.
+    pub fn rotate_cw(&self) -> Grid<T> {
+        Grid::new(
+            (0..self.width())
+                .map(|x| (0..self.height()).rev().map(|y| self.cells[y][x]).collect())
+                .collect(),
+        )
+    }
+
+    /// Return a copy of the grid rotated 90° counter-clockwise.  The last column (read top to
+    /// bottom) becomes the first row.
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        Grid::new(
+            (0..self.width())
+                .rev()
+                .map(|x| (0..self.height()).map(|y| self.cells[y][x]).collect())
+                .collect(),
+        )
+    }
+
+    /// Return a copy of the grid mirrored left-to-right.
+    pub fn flip_h(&self) -> Grid<T> {
+        Grid::new(
+            self.cells
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        )
+    }
+
+    /// Return a copy of the grid mirrored top-to-bottom.
+    pub fn flip_v(&self) -> Grid<T> {
+        Grid::new(self.cells.iter().rev().cloned().collect())
+    }
+
+    /// Return a copy of the grid reflected across its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::new(self.cols())
+    }
+
+    /// Copy out the region covered by `rect`.  Panics if the region extends beyond the grid.
+    pub fn subgrid(&self, rect: Rect<2>) -> Grid<T> {
+        if rect.is_empty() {
+            return Grid::new(vec![]);
+        }
+        let outside = "subgrid extends beyond the grid";
+        let [x0, y0] = <[usize; 2]>::try_from(rect.min).expect(outside);
+        let [x1, y1] = <[usize; 2]>::try_from(rect.max).expect(outside);
+        assert!(x1 <= self.width() && y1 <= self.height(), "{outside}");
+        Grid::new(
+            self.cells[y0..y1]
+                .iter()
+                .map(|row| row[x0..x1].to_vec())
+                .collect(),
+        )
+    }
+
+    /// Repeat the grid `nx` times horizontally and `ny` times vertically.
+    pub fn tile(&self, nx: usize, ny: usize) -> Grid<T> {
+        let rows: Vec<Vec<T>> = self.cells.iter().map(|row| row.repeat(nx)).collect();
+        Grid::new((0..ny).flat_map(|_| rows.iter().cloned()).collect())
+    }
+
+    /// Surround the grid with a border `n` cells thick, filled with `value`.
+    pub fn pad(&self, value: T, n: usize) -> Grid<T> {
+        let width = self.width() + 2 * n;
+        let border = vec![value; n];
+
+        let mut cells = vec![vec![value; width]; n];
+        cells.extend(
+            self.cells
+                .iter()
+                .map(|row| [border.as_slice(), row, border.as_slice()].concat()),
+        );
+        cells.extend(vec![vec![value; width]; n]);
+
+        Grid::new(cells)
+    }
+
+    /// Iterate over the cells starting at `start` (inclusive) and moving by `step` each time,
+    /// until the edge of the grid.  Directions such as [CardDir](crate::direction::CardDir) and
+    /// [CardOrdDir](crate::direction::CardOrdDir) can be used as the step.
+    pub fn ray(
+        &self,
+        start: Point<2>,
+        step: impl Into<Point<2>>,
+    ) -> impl Iterator<Item = Cell<T>> + '_ {
+        let step = step.into();
+        let len = if step == Point::new(&[0, 0]) {
+            1
+        } else {
+            usize::MAX
+        };
+
+        std::iter::successors(Some(start), move |p| Some(*p + step))
+            .take(len)
+            .map_while(|p| self.cellp(p))
+    }
+
+    /// Iterate over the cells at every lattice point on the segment from `a` to `b` (both
+    /// inclusive), skipping any that fall outside the grid.
+    pub fn line(&self, a: Point<2>, b: Point<2>) -> impl Iterator<Item = Cell<T>> + '_ {
+        lattice_points(a, b).filter_map(|p| self.cellp(p))
+    }
+
+    /// Get the cell at the given point, if it's within the grid.
+    pub fn cellp(&self, p: Point<2>) -> Option<Cell<T>> {
+        if p.x() < 0 || p.y() < 0 {
+            return None;
+        }
+        self.getp(p).map(|data| Cell::new(p, data))
     }
 
     /// Get cells adjacent to the given point in the cardinal directions.  Origin is up-left from
@@ -86,30 +206,13 @@ impl<T: Copy> Grid<T> {
     ///
     /// Four cells will always be returned, in the following order relative to the given point:
     ///  
-    /// ```
+    /// ```text
     /// [ ⬆️, ⬅️, ➡️, ⬇️, ]
     /// ```
     ///
-    /// In words: up left, up, up right, left, right, down left, down, down right.
+    /// In words: up, left, right, down.
     pub fn adj_4(&self, loc: Point<2>) -> Adj4<T> {
-        Adj4::new(
-            [
-                (Some(loc.x()), loc.y().checked_sub(1)),
-                (loc.x().checked_sub(1), Some(loc.y())),
-                (loc.x().checked_add(1), Some(loc.y())),
-                (Some(loc.x()), loc.y().checked_add(1)),
-            ]
-            .map(|(adj_x, adj_y)| {
-                adj_x.and_then(|adj_x| {
-                    adj_y.and_then(|adj_y| {
-                        self.cells.get(adj_y as usize).and_then(|row| {
-                            row.get(adj_x as usize)
-                                .map(|cell_data| Cell::new([adj_x, adj_y].into(), *cell_data))
-                        })
-                    })
-                })
-            }),
-        )
+        GridLike::adj_4(self, loc)
     }
 
     /// Get cells adjacent to the given point in cardinal and ordinal directions (ie,
@@ -120,7 +223,7 @@ impl<T: Copy> Grid<T> {
     ///
     /// Eight cells will always be returned, in the following order relative to the given point:
     ///  
-    /// ```
+    /// ```text
     /// [
     ///   ↖️, ⬆️, ↗️,
     ///   ⬅️,    ➡️,
@@ -129,42 +232,105 @@ impl<T: Copy> Grid<T> {
     /// ```
     ///
     /// In words: up left, up, up right, left, right, down left, down, down right.
-    pub fn adj_8(&self, x: usize, y: usize) -> Adj8<T> {
-        Adj8::new(
-            [
-                (x.checked_sub(1), y.checked_sub(1)),
-                (Some(x), y.checked_sub(1)),
-                (x.checked_add(1), y.checked_sub(1)),
-                (x.checked_sub(1), Some(y)),
-                (x.checked_add(1), Some(y)),
-                (x.checked_sub(1), y.checked_add(1)),
-                (Some(x), y.checked_add(1)),
-                (x.checked_add(1), y.checked_add(1)),
-            ]
-            .map(|(adj_x, adj_y)| {
-                adj_x.and_then(|adj_x| {
-                    adj_y.and_then(|adj_y| {
-                        self.cells.get(adj_y).and_then(|row| {
-                            row.get(adj_x)
-                                .map(|cell_data| Cell::new([adj_x, adj_y].into(), *cell_data))
-                        })
-                    })
+    pub fn adj_8(&self, loc: Point<2>) -> Adj8<T> {
+        GridLike::adj_8(self, loc)
+    }
+
+    /// Iterate over the cells neighboring the given point, paired with the direction of each
+    /// neighbor.  Neighbors outside the grid are skipped.
+    ///
+    /// ```
+    /// # use aoc2024::grid::{Grid, Cardinal};
+    /// let g = Grid::from([[1, 2], [3, 4]]);
+    /// for (dir, cell) in g.neighbors([0, 0].into(), Cardinal) {
+    ///     println!("{dir} {}", cell.data);
+    /// }
+    /// ```
+    pub fn neighbors<N: Neighborhood>(
+        &self,
+        loc: Point<2>,
+        kind: N,
+    ) -> impl Iterator<Item = (N::Dir, Cell<T>)> + use<'_, T, N> {
+        GridLike::neighbors(self, loc, kind)
+    }
+}
+
+/// A pattern to search for in a grid.  `None` cells are wildcards, which match anything,
+/// including positions that fall outside the grid.
+pub type Kernel<T> = Grid<Option<T>>;
+
+/// Which orientations of a kernel [Grid::find_kernel] should try.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Symmetry {
+    /// Only the kernel as given.
+    Fixed,
+    /// The kernel rotated by 0°, 90°, 180°, and 270°.
+    Rotations,
+    /// Every rotation of the kernel and of its mirror image.
+    All,
+}
+
+impl<T: Copy> Kernel<T> {
+    /// Build a kernel from text, one row per line.  `wildcard` becomes None, and every other char
+    /// is converted with `f`.  Short lines are padded with wildcards to the longest line.
+    pub fn from_pattern(pattern: &str, wildcard: char, f: impl Fn(char) -> T) -> Self {
+        let width = pattern
+            .lines()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or(0);
+        Grid::new(
+            pattern
+                .lines()
+                .map(|line| {
+                    let mut row: Vec<Option<T>> = line
+                        .chars()
+                        .map(|c| (c != wildcard).then(|| f(c)))
+                        .collect();
+                    row.resize(width, None);
+                    row
                 })
-            }),
+                .collect(),
         )
     }
 }
 
+impl<T: Copy + PartialEq> Kernel<T> {
+    /// Every distinct orientation of the kernel allowed by the symmetry, starting with the kernel
+    /// itself.
+    pub fn orientations(&self, symmetry: Symmetry) -> Vec<Kernel<T>> {
+        let mut candidates = vec![self.clone()];
+
+        if symmetry != Symmetry::Fixed {
+            for _ in 0..3 {
+                candidates.push(candidates[candidates.len() - 1].rotate_cw());
+            }
+        }
+
+        if symmetry == Symmetry::All {
+            let mirrored: Vec<Kernel<T>> = candidates.iter().map(|k| k.flip_h()).collect();
+            candidates.extend(mirrored);
+        }
+
+        let mut orientations: Vec<Kernel<T>> = Vec::with_capacity(candidates.len());
+        for k in candidates {
+            if !orientations.contains(&k) {
+                orientations.push(k);
+            }
+        }
+        orientations
+    }
+}
+
 impl<T: Copy + PartialEq> Grid<T> {
-    pub fn match_kernel<const D: usize>(&self, kernel: [[Option<T>; D]; D], pos: Point<2>) -> bool {
-        for ky in 0..D {
-            for kx in 0..D {
-                if let Some(k) = kernel[ky][kx] {
-                    if self
-                        .get(pos.x() as usize + kx, pos.y() as usize + ky)
-                        .and_then(|c| (c == k).then_some(()))
-                        .is_none()
-                    {
+    /// Check whether the kernel matches with its top-left corner at `pos`.  The kernel may hang
+    /// off the edges of the grid, as long as only wildcards fall outside.
+    pub fn match_kernel(&self, kernel: &Kernel<T>, pos: Point<2>) -> bool {
+        for (ky, kernel_row) in kernel.cells.iter().enumerate() {
+            for (kx, kernel_cell) in kernel_row.iter().enumerate() {
+                if let Some(k) = *kernel_cell {
+                    let p = pos + [kx, ky].try_into().unwrap();
+                    if p.x() < 0 || p.y() < 0 || self.getp(p) != Some(k) {
                         return false;
                     }
                 }
@@ -172,6 +338,299 @@ impl<T: Copy + PartialEq> Grid<T> {
         }
         true
     }
+
+    /// Find every position (the top-left corner of the kernel) where the kernel matches, trying
+    /// each orientation allowed by the symmetry.  A position is reported once per orientation
+    /// that matches there.  An empty kernel matches nowhere.
+    pub fn find_kernel(&self, kernel: &Kernel<T>, symmetry: Symmetry) -> Vec<Point<2>> {
+        let mut matches = Vec::new();
+        if kernel.width() == 0 || kernel.height() == 0 {
+            return matches;
+        }
+
+        for k in kernel.orientations(symmetry) {
+            for y in 1 - k.height() as i64..self.height() as i64 {
+                for x in 1 - k.width() as i64..self.width() as i64 {
+                    let pos = [x, y].into();
+                    if self.match_kernel(&k, pos) {
+                        matches.push(pos);
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+/// Build a grid from a fixed-size array of rows.
+impl<T: Copy, const W: usize, const H: usize> From<[[T; W]; H]> for Grid<T> {
+    fn from(cells: [[T; W]; H]) -> Self {
+        Grid::new(cells.iter().map(|row| row.to_vec()).collect())
+    }
+}
+
+/// Operations shared by every grid representation, so a solution can switch between dense,
+/// sparse, and wrapping storage without rewriting its logic.
+pub trait GridLike<T: Copy> {
+    /// The number of columns spanned by the grid.
+    fn width(&self) -> usize;
+
+    /// The number of rows spanned by the grid.
+    fn height(&self) -> usize;
+
+    /// Get the data at the given point, if any.
+    fn getp(&self, p: Point<2>) -> Option<T>;
+
+    /// Set data in the grid using a Point as coordinates.
+    fn setp(&mut self, p: Point<2>, new_data: T);
+
+    /// Get the cell at the given point, if any.  The cell's position is where the data actually
+    /// lives, which may differ from `p` for grids that wrap around their edges.
+    fn cellp(&self, p: Point<2>) -> Option<Cell<T>> {
+        self.getp(p).map(|data| Cell::new(p, data))
+    }
+
+    /// Iterate over the cells neighboring the given point, paired with the direction of each
+    /// neighbor.  See [Grid::neighbors].
+    fn neighbors<N: Neighborhood>(
+        &self,
+        loc: Point<2>,
+        kind: N,
+    ) -> impl Iterator<Item = (N::Dir, Cell<T>)> {
+        kind.steps()
+            .into_iter()
+            .filter_map(move |(dir, step)| self.cellp(loc + step).map(|cell| (dir, cell)))
+    }
+
+    /// Get cells adjacent to the given point in the cardinal directions, in the same order as
+    /// [Grid::adj_4].
+    fn adj_4(&self, loc: Point<2>) -> Adj4<T> {
+        Adj4::new([[0, -1], [-1, 0], [1, 0], [0, 1]].map(|d| self.cellp(loc + d.into())))
+    }
+
+    /// Get cells adjacent to the given point in cardinal and ordinal directions, in the same
+    /// order as [Grid::adj_8].
+    fn adj_8(&self, loc: Point<2>) -> Adj8<T> {
+        Adj8::new(
+            [
+                [-1, -1],
+                [0, -1],
+                [1, -1],
+                [-1, 0],
+                [1, 0],
+                [-1, 1],
+                [0, 1],
+                [1, 1],
+            ]
+            .map(|d| self.cellp(loc + d.into())),
+        )
+    }
+}
+
+impl<T: Copy> GridLike<T> for Grid<T> {
+    fn width(&self) -> usize {
+        Grid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Grid::height(self)
+    }
+
+    fn getp(&self, p: Point<2>) -> Option<T> {
+        Grid::getp(self, p)
+    }
+
+    fn setp(&mut self, p: Point<2>, new_data: T) {
+        Grid::setp(self, p, new_data)
+    }
+
+    fn cellp(&self, p: Point<2>) -> Option<Cell<T>> {
+        Grid::cellp(self, p)
+    }
+}
+
+/// A set of offsets to visit with [Grid::neighbors], each labeled with a direction.
+pub trait Neighborhood {
+    /// What each neighbor is labeled with.
+    type Dir;
+    type Steps: IntoIterator<Item = (Self::Dir, Point<2>)>;
+
+    /// The offsets to each neighbor, with their labels.
+    fn steps(self) -> Self::Steps;
+}
+
+/// The four neighbors sharing an edge with a cell: up, left, right, down.
+#[derive(Debug, Clone, Copy)]
+pub struct Cardinal;
+
+/// The four neighbors sharing only a corner with a cell: up left, up right, down left, down
+/// right.
+#[derive(Debug, Clone, Copy)]
+pub struct Diagonal;
+
+/// All eight neighbors of a cell, in the same order as [Grid::adj_8].
+#[derive(Debug, Clone, Copy)]
+pub struct Surrounding;
+
+impl Neighborhood for Cardinal {
+    type Dir = CardDir;
+    type Steps = [(CardDir, Point<2>); 4];
+
+    fn steps(self) -> Self::Steps {
+        use CardDir::*;
+        [Up, Left, Right, Down].map(|dir| (dir, dir.into()))
+    }
+}
+
+impl Neighborhood for Diagonal {
+    type Dir = CardOrdDir;
+    type Steps = [(CardOrdDir, Point<2>); 4];
+
+    fn steps(self) -> Self::Steps {
+        use CardOrdDir::*;
+        [UpLeft, UpRight, DownLeft, DownRight].map(|dir| (dir, dir.into()))
+    }
+}
+
+impl Neighborhood for Surrounding {
+    type Dir = CardOrdDir;
+    type Steps = [(CardOrdDir, Point<2>); 8];
+
+    fn steps(self) -> Self::Steps {
+        CardOrdDir::all().map(|dir| (dir, dir.into()))
+    }
+}
+
+/// A custom list of offsets.  Each neighbor is labeled with the offset used to reach it.
+impl<'a> Neighborhood for &'a [Point<2>] {
+    type Dir = Point<2>;
+    type Steps =
+        std::iter::Map<std::slice::Iter<'a, Point<2>>, fn(&Point<2>) -> (Point<2>, Point<2>)>;
+
+    fn steps(self) -> Self::Steps {
+        self.iter().map(|step| (*step, *step))
+    }
+}
+
+/// A cell whose data differs between two grids.  Produced by Grid::diff.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct CellDiff<T> {
+    pub pos: Point<2>,
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// List the cells whose data differs between this grid (the old state) and `other` (the new
+    /// state), in row order.  Only the area the two grids have in common is compared.
+    pub fn diff(&self, other: &Grid<T>) -> Vec<CellDiff<T>> {
+        self.cells
+            .iter()
+            .zip(&other.cells)
+            .enumerate()
+            .flat_map(|(y, (old_row, new_row))| {
+                old_row
+                    .iter()
+                    .zip(new_row)
+                    .enumerate()
+                    .filter(|(_, (old, new))| old != new)
+                    .map(move |(x, (old, new))| CellDiff {
+                        pos: [x, y].try_into().unwrap(),
+                        old: *old,
+                        new: *new,
+                    })
+            })
+            .collect()
+    }
+}
+
+impl<T: Copy + PartialEq + Display> Grid<T> {
+    /// Render `other` (the new state) with the cells that changed since this grid (the old
+    /// state) highlighted.
+    pub fn diff_string(&self, other: &Grid<T>) -> String {
+        (0..other.height())
+            .map(|y| format!("{}\n", diff_row(other, self, y, color::Green).0))
+            .collect()
+    }
+}
+
+/// Render row `y` of `grid`, highlighting cells that differ from `other`.  Returns the row and
+/// its width in columns, not counting escape codes.
+fn diff_row<T: Copy + PartialEq + Display>(
+    grid: &Grid<T>,
+    other: &Grid<T>,
+    y: usize,
+    highlight: impl color::Color + Copy,
+) -> (String, usize) {
+    let mut row = String::new();
+    let mut width = 0;
+
+    for x in 0..grid.cells.get(y).map(|r| r.len()).unwrap_or(0) {
+        let cell = grid.cells[y][x].to_string();
+        width += cell.chars().count();
+        if other.get(x, y) == grid.get(x, y) {
+            row.push_str(&cell);
+        } else {
+            row.push_str(&format!(
+                "{}{}{cell}{}",
+                color::Fg(highlight),
+                style::Bold,
+                style::Reset
+            ));
+        }
+    }
+
+    (row, width)
+}
+
+/// Render two grids next to each other, highlighting the cells where they differ.  Used by
+/// [assert_grid_eq] to explain failures.
+pub fn side_by_side<T: Copy + PartialEq + Display>(left: &Grid<T>, right: &Grid<T>) -> String {
+    let left_rows: Vec<(String, usize)> = (0..left.height())
+        .map(|y| diff_row(left, right, y, color::Red))
+        .collect();
+    let right_rows: Vec<(String, usize)> = (0..right.height())
+        .map(|y| diff_row(right, left, y, color::Green))
+        .collect();
+
+    let left_title = format!("left ({}x{})", left.width(), left.height());
+    let right_title = format!("right ({}x{})", right.width(), right.height());
+    let col_width = left_rows
+        .iter()
+        .map(|r| r.1)
+        .chain([left_title.len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{left_title:col_width$} | {right_title}\n");
+    for y in 0..left_rows.len().max(right_rows.len()) {
+        let (l, l_width) = left_rows.get(y).cloned().unwrap_or_default();
+        let (r, _) = right_rows.get(y).cloned().unwrap_or_default();
+        out.push_str(&format!(
+            "{l}{pad} | {r}\n",
+            pad = " ".repeat(col_width - l_width)
+        ));
+    }
+    out
+}
+
+/// Assert that two grids are equal.  On failure, the grids are printed side by side with the
+/// differing cells highlighted.
+#[macro_export]
+macro_rules! assert_grid_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    panic!(
+                        "assertion `left == right` failed\n{}",
+                        $crate::grid::side_by_side(left, right)
+                    );
+                }
+            }
+        }
+    };
 }
 
 impl<T> Display for Grid<T>
@@ -189,6 +648,113 @@ where
     }
 }
 
+/// A set of points drawn over a grid by a [Renderer].  Any of the glyph and colors may be left
+/// unset, in which case whatever is underneath shows through.
+#[derive(Debug, Default)]
+pub struct Layer {
+    pub points: HashSet<Point<2>>,
+    pub fg: Option<Box<dyn color::Color>>,
+    pub bg: Option<Box<dyn color::Color>>,
+    pub glyph: Option<char>,
+}
+
+impl Layer {
+    pub fn new(points: impl IntoIterator<Item = Point<2>>) -> Self {
+        Self {
+            points: points.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the foreground color for this layer's points.
+    pub fn fg(mut self, c: impl color::Color + 'static) -> Self {
+        self.fg = Some(Box::new(c));
+        self
+    }
+
+    /// Set the background color for this layer's points.
+    pub fn bg(mut self, c: impl color::Color + 'static) -> Self {
+        self.bg = Some(Box::new(c));
+        self
+    }
+
+    /// Replace the glyph drawn at this layer's points.
+    pub fn glyph(mut self, c: char) -> Self {
+        self.glyph = Some(c);
+        self
+    }
+}
+
+/// Draws a grid to the terminal, one glyph per cell, with layers of colored points on top.
+/// Layers are drawn in the order they were added, so later layers win where they overlap.
+pub struct Renderer<'a, T: Copy> {
+    grid: &'a Grid<T>,
+    glyph: Box<dyn Fn(T) -> char + 'a>,
+    layers: Vec<Layer>,
+}
+
+impl<'a, T: Copy> Renderer<'a, T> {
+    /// Create a renderer for the grid, using `glyph` to pick the character for each cell.
+    pub fn new(grid: &'a Grid<T>, glyph: impl Fn(T) -> char + 'a) -> Self {
+        Self {
+            grid,
+            glyph: Box::new(glyph),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Add a layer on top of any existing layers.
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Write the grid to `out`.  When `ansi` is false, only the glyphs are written, with no
+    /// color escape codes.
+    pub fn render(&self, out: &mut impl Write, ansi: bool) -> io::Result<()> {
+        for (y, row) in self.grid.cells.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let p: Point<2> = [x, y].try_into().unwrap();
+                let mut glyph = (self.glyph)(*cell);
+                let mut fg = None;
+                let mut bg = None;
+
+                for layer in self.layers.iter().filter(|l| l.points.contains(&p)) {
+                    glyph = layer.glyph.unwrap_or(glyph);
+                    fg = layer.fg.as_deref().or(fg);
+                    bg = layer.bg.as_deref().or(bg);
+                }
+
+                if ansi {
+                    if let Some(fg) = fg {
+                        write!(out, "{}", color::Fg(fg))?;
+                    }
+                    if let Some(bg) = bg {
+                        write!(out, "{}", color::Bg(bg))?;
+                    }
+                    write!(out, "{glyph}")?;
+                    if fg.is_some() || bg.is_some() {
+                        write!(out, "{}", style::Reset)?;
+                    }
+                } else {
+                    write!(out, "{glyph}")?;
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Write the grid to stdout, with colors only if stdout is a terminal.
+    pub fn print(&self) -> io::Result<()> {
+        let stdout = io::stdout();
+        let ansi = termion::is_tty(&stdout);
+        let mut lock = stdout.lock();
+        self.render(&mut lock, ansi)?;
+        lock.flush()
+    }
+}
+
 /// A representation of cells adjacent to a point in the cardinal directions.  Produced by Grid::adj_4.
 #[derive(PartialEq, Debug)]
 pub struct Adj4<T: Copy> {
@@ -266,16 +832,17 @@ impl<T> Cell<T> {
 #[cfg(test)]
 mod grid_tests {
     use super::*;
+    use crate::assert_grid_eq;
 
     #[test]
     fn empty_test() {
         let g: Grid<bool> = Grid { cells: vec![] };
         assert_eq!(
-            g.adj_8(0, 0),
+            g.adj_8([0, 0].into()),
             Adj8::new([None, None, None, None, None, None, None, None])
         );
         assert_eq!(
-            g.adj_8(1, 1),
+            g.adj_8([1, 1].into()),
             Adj8::new([None, None, None, None, None, None, None, None])
         );
     }
@@ -287,7 +854,7 @@ mod grid_tests {
         };
         #[rustfmt::skip]
         assert_eq!(
-            g.adj_8(0, 0),
+            g.adj_8([0, 0].into()),
             Adj8::new([
                 None,                              None,                              None,
                 None,                                                                 Some(Cell::new([1,0].into(), 2)),
@@ -296,7 +863,7 @@ mod grid_tests {
         );
         #[rustfmt::skip]
         assert_eq!(
-            g.adj_8(3, 0),
+            g.adj_8([3, 0].into()),
             Adj8::new([
                 None,                              None,                              None,
                 Some(Cell::new([2,0].into(), 3)),                                     Some(Cell::new([4,0].into(), 5)),
@@ -317,7 +884,7 @@ mod grid_tests {
         };
         #[rustfmt::skip]
         assert_eq!(
-            g.adj_8(0, 0),
+            g.adj_8([0, 0].into()),
             Adj8::new([
                 None,                              None,                              None,
                 None,                                                                 Some(Cell::new([1,0].into(), 2)),
@@ -326,7 +893,7 @@ mod grid_tests {
         );
         #[rustfmt::skip]
         assert_eq!(
-            g.adj_8(1, 1),
+            g.adj_8([1, 1].into()),
             Adj8::new([
                 Some(Cell::new([0, 0].into(), 1)), Some(Cell::new([1, 0].into(), 2)), Some(Cell::new([2,0].into(), 3)),
                 Some(Cell::new([0, 1].into(), 4)),                                    Some(Cell::new([2,1].into(), 6)),
@@ -335,6 +902,46 @@ mod grid_tests {
         );
     }
 
+    #[test]
+    fn neighbors_test() {
+        let g = Grid::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+
+        assert_eq!(
+            g.neighbors([0, 0].into(), Cardinal)
+                .map(|(dir, cell)| (dir, cell.data))
+                .collect::<Vec<_>>(),
+            vec![(CardDir::Right, 2), (CardDir::Down, 4)]
+        );
+        assert_eq!(
+            g.neighbors([1, 1].into(), Diagonal)
+                .map(|(dir, cell)| (dir, cell.data))
+                .collect::<Vec<_>>(),
+            vec![
+                (CardOrdDir::UpLeft, 1),
+                (CardOrdDir::UpRight, 3),
+                (CardOrdDir::DownLeft, 7),
+                (CardOrdDir::DownRight, 9)
+            ]
+        );
+        assert_eq!(
+            g.neighbors([2, 2].into(), Surrounding)
+                .map(|(_, cell)| cell.data)
+                .collect::<Vec<_>>(),
+            vec![5, 6, 8]
+        );
+
+        let knight: &[Point<2>] = &[[1, 2].into(), [2, 1].into(), [-1, 2].into()];
+        assert_eq!(
+            g.neighbors([0, 0].into(), knight)
+                .map(|(step, cell)| (step, cell.pos))
+                .collect::<Vec<_>>(),
+            vec![
+                ([1, 2].into(), [1, 2].into()),
+                ([2, 1].into(), [2, 1].into())
+            ]
+        );
+    }
+
     #[test]
     fn grid_set_col_test() {
         let mut g: Grid<u8> = Grid {
@@ -349,14 +956,341 @@ mod grid_tests {
         g.set_col(1, &[13, 11, 12]);
 
         #[rustfmt::skip]
+        assert_grid_eq!(
+            g,
+            Grid::from([
+                [1, 13, 3],
+                [4, 11, 6],
+                [7, 12, 9]
+            ])
+        );
+    }
+
+    fn grid_2x3() -> Grid<u8> {
+        Grid {
+            #[rustfmt::skip]
+            cells: vec![
+                vec![1, 2, 3],
+                vec![4, 5, 6],
+            ],
+        }
+    }
+
+    #[test]
+    fn grid_rotate_test() {
+        let g = grid_2x3();
+
+        #[rustfmt::skip]
+        assert_grid_eq!(
+            g.rotate_cw(),
+            Grid::from([
+                [4, 1],
+                [5, 2],
+                [6, 3],
+            ])
+        );
+        #[rustfmt::skip]
+        assert_grid_eq!(
+            g.rotate_ccw(),
+            Grid::from([
+                [3, 6],
+                [2, 5],
+                [1, 4],
+            ])
+        );
+
+        assert_grid_eq!(g.rotate_cw().rotate_ccw(), g);
+        assert_grid_eq!(g.rotate_ccw().rotate_cw(), g);
+        assert_grid_eq!(g.rotate_cw().rotate_cw(), g.flip_h().flip_v());
+        assert_grid_eq!(g.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), g);
+    }
+
+    #[test]
+    fn grid_flip_test() {
+        let g = grid_2x3();
+
+        assert_grid_eq!(g.flip_h(), Grid::from([[3, 2, 1], [6, 5, 4]]));
+        assert_grid_eq!(g.flip_v(), Grid::from([[4, 5, 6], [1, 2, 3]]));
+        assert_grid_eq!(g.flip_h().flip_h(), g);
+        assert_grid_eq!(g.flip_v().flip_v(), g);
+    }
+
+    #[test]
+    fn grid_transpose_test() {
+        let g = grid_2x3();
+
+        assert_grid_eq!(g.transpose(), Grid::from([[1, 4], [2, 5], [3, 6]]));
+        assert_grid_eq!(g.transpose().transpose(), g);
+        assert_grid_eq!(g.transpose(), g.rotate_cw().flip_h());
+
+        let empty: Grid<u8> = Grid::new(vec![]);
+        assert_eq!(empty.transpose().area(), 0);
+        assert!(empty.cols().is_empty());
+    }
+
+    #[test]
+    fn grid_subgrid_test() {
+        let g = grid_2x3();
+
+        assert_grid_eq!(
+            g.subgrid(Rect::new([1, 0], [3, 2])),
+            Grid::from([[2, 3], [5, 6]])
+        );
+        assert_grid_eq!(
+            g.subgrid(Rect::new([0, 1], [3, 2])),
+            Grid::from([[4, 5, 6]])
+        );
+        assert_grid_eq!(g.subgrid(Rect::from(&g)), g);
+        assert_eq!(g.subgrid(Rect::new([1, 1], [1, 2])).area(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "subgrid extends beyond the grid")]
+    fn grid_subgrid_outside_test() {
+        grid_2x3().subgrid(Rect::new([2, 0], [4, 2]));
+    }
+
+    #[test]
+    fn grid_tile_test() {
+        let g = grid_2x3();
+
+        #[rustfmt::skip]
+        assert_grid_eq!(
+            g.tile(2, 2),
+            Grid::from([
+                [1, 2, 3, 1, 2, 3],
+                [4, 5, 6, 4, 5, 6],
+                [1, 2, 3, 1, 2, 3],
+                [4, 5, 6, 4, 5, 6],
+            ])
+        );
+        assert_grid_eq!(g.tile(2, 3).subgrid(Rect::new([3, 2], [6, 4])), g);
+    }
+
+    #[test]
+    fn grid_pad_test() {
+        let g = grid_2x3();
+
+        #[rustfmt::skip]
+        assert_grid_eq!(
+            g.pad(0, 1),
+            Grid::from([
+                [0, 0, 0, 0, 0],
+                [0, 1, 2, 3, 0],
+                [0, 4, 5, 6, 0],
+                [0, 0, 0, 0, 0],
+            ])
+        );
+        assert_grid_eq!(g.pad(0, 2).subgrid(Rect::new([2, 2], [5, 4])), g);
+        assert_eq!(format!("{}", g.pad(0, 1)), "00000\n01230\n04560\n00000\n");
+    }
+
+    #[test]
+    fn kernel_from_pattern_test() {
+        let k = Kernel::from_pattern("a.\n.b\nc.", '.', |c| c);
         assert_eq!(
-            g.cells,
+            k,
+            Grid::from([[Some('a'), None], [None, Some('b')], [Some('c'), None]])
+        );
+        assert_eq!(k.width(), 2);
+        assert_eq!(k.height(), 3);
+
+        // short rows are padded with wildcards
+        let k = Kernel::from_pattern("M.S\n.A\nM.S", '.', |c| c);
+        assert_eq!(k.width(), 3);
+        assert_eq!(k.get(2, 1), Some(None));
+        assert_eq!(k.orientations(Symmetry::Rotations).len(), 4);
+    }
+
+    #[test]
+    fn kernel_orientations_test() {
+        let k = Kernel::from_pattern("ab", '.', |c| c);
+        assert_eq!(k.orientations(Symmetry::Fixed).len(), 1);
+        assert_eq!(k.orientations(Symmetry::Rotations).len(), 4);
+        assert_eq!(k.orientations(Symmetry::All).len(), 4);
+
+        let k = Kernel::from_pattern("ab\nc.", '.', |c| c);
+        assert_eq!(k.orientations(Symmetry::All).len(), 8);
+
+        let k = Kernel::from_pattern("a.a\n.b.\na.a", '.', |c| c);
+        assert_eq!(k.orientations(Symmetry::All).len(), 1);
+    }
+
+    #[test]
+    fn find_kernel_test() {
+        let g = Grid::new(
+            ["abab", "baba", "abab"]
+                .iter()
+                .map(|l| l.chars().collect())
+                .collect(),
+        );
+
+        let k = Kernel::from_pattern("ab\n.a", '.', |c| c);
+        assert_eq!(
+            g.find_kernel(&k, Symmetry::Fixed),
+            vec![[0, 0].into(), [2, 0].into(), [1, 1].into()]
+        );
+
+        let k = Kernel::from_pattern("ab", '.', |c| c);
+        assert_eq!(g.find_kernel(&k, Symmetry::Fixed).len(), 5);
+        // "ab" reads left-to-right, right-to-left, top-to-bottom and bottom-to-top
+        assert_eq!(g.find_kernel(&k, Symmetry::Rotations).len(), 5 + 4 + 4 + 4);
+
+        let k = Kernel::from_pattern("", '.', |c| c);
+        assert!(g.find_kernel(&k, Symmetry::All).is_empty());
+    }
+
+    #[test]
+    fn find_kernel_off_edge_test() {
+        let g: Grid<u8> = Grid::from([[1, 2], [3, 4]]);
+
+        // wildcards may hang off the grid, but concrete cells may not
+        let k = Grid::from([[None, None], [None, Some(1)]]);
+        assert_eq!(g.find_kernel(&k, Symmetry::Fixed), vec![[-1, -1].into()]);
+        assert!(g.match_kernel(&k, [-1, -1].into()));
+        assert!(!g.match_kernel(&k, [1, 1].into()));
+
+        let k = Grid::from([[Some(4), Some(1)]]);
+        assert!(g.find_kernel(&k, Symmetry::Fixed).is_empty());
+    }
+
+    #[test]
+    fn ray_test() {
+        let g = Grid::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        let data =
+            |cells: &mut dyn Iterator<Item = Cell<u8>>| cells.map(|c| c.data).collect::<Vec<_>>();
+
+        assert_eq!(
+            data(&mut g.ray([0, 0].into(), CardOrdDir::DownRight)),
+            vec![1, 5, 9]
+        );
+        assert_eq!(
+            data(&mut g.ray([2, 1].into(), CardDir::Left)),
+            vec![6, 5, 4]
+        );
+        assert_eq!(data(&mut g.ray([1, 0].into(), CardDir::Up)), vec![2]);
+        assert_eq!(data(&mut g.ray([0, 2].into(), [2, -1])), vec![7, 6]);
+        assert_eq!(data(&mut g.ray([1, 1].into(), [0, 0])), vec![5]);
+        assert_eq!(data(&mut g.ray([3, 0].into(), CardDir::Down)), vec![]);
+    }
+
+    #[test]
+    fn line_test() {
+        let g = Grid::new(
+            (0..5)
+                .map(|y| (0..5).map(|x| y * 5 + x).collect())
+                .collect(),
+        );
+        let positions = |a: [i64; 2], b: [i64; 2]| {
+            g.line(a.into(), b.into())
+                .map(|c| c.pos)
+                .collect::<Vec<Point<2>>>()
+        };
+
+        assert_eq!(
+            positions([0, 0], [4, 2]),
+            vec![[0, 0].into(), [2, 1].into(), [4, 2].into()]
+        );
+        assert_eq!(
+            positions([4, 4], [0, 0]),
+            (0..5).rev().map(|n| [n, n].into()).collect::<Vec<_>>()
+        );
+        assert_eq!(positions([1, 1], [1, 1]), vec![[1, 1].into()]);
+        assert_eq!(
+            positions([-2, 0], [4, 3]),
+            vec![[0, 1].into(), [2, 2].into(), [4, 3].into()]
+        );
+    }
+
+    #[test]
+    fn diff_test() {
+        let old = grid_2x3();
+        let mut new = old.clone();
+        new.set(0, 1, 9);
+        new.set(2, 1, 0);
+
+        assert_eq!(
+            old.diff(&new),
             vec![
-                vec![1, 13, 3],
-                vec![4, 11, 6],
-                vec![7, 12, 9]
+                CellDiff {
+                    pos: [0, 1].into(),
+                    old: 4,
+                    new: 9
+                },
+                CellDiff {
+                    pos: [2, 1].into(),
+                    old: 6,
+                    new: 0
+                },
             ]
         );
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(old.diff(&old.pad(0, 1)).len(), 6);
+
+        let green = format!("{}{}", color::Fg(color::Green), style::Bold);
+        let reset = style::Reset;
+        assert_eq!(
+            old.diff_string(&new),
+            format!("123\n{green}9{reset}5{green}0{reset}\n")
+        );
+    }
+
+    #[test]
+    fn side_by_side_test() {
+        let left = Grid::from([[1, 2], [3, 4]]);
+        let right = Grid::from([[1, 2, 0], [3, 5, 0], [0, 0, 0]]);
+
+        let plain: String = side_by_side(&left, &right)
+            .replace(&color::Fg(color::Red).to_string(), "")
+            .replace(&color::Fg(color::Green).to_string(), "")
+            .replace(&style::Bold.to_string(), "")
+            .replace(&style::Reset.to_string(), "");
+
+        assert_eq!(
+            plain,
+            "left (2x2) | right (3x3)\n\
+             12         | 120\n\
+             34         | 350\n\
+             \x20          | 000\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "left (3x2) | right (3x2)")]
+    fn assert_grid_eq_failure_test() {
+        assert_grid_eq!(grid_2x3(), grid_2x3().flip_h());
+    }
+
+    #[test]
+    fn renderer_plain_test() {
+        let g = grid_2x3();
+        let r = Renderer::new(&g, |n| char::from(b'0' + n))
+            .layer(Layer::new([[0, 0].into(), [1, 0].into()]).glyph('#'))
+            .layer(Layer::new([[1, 0].into()]).glyph('@'));
+
+        let mut out = Vec::new();
+        r.render(&mut out, false).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "#@3\n456\n");
+    }
+
+    #[test]
+    fn renderer_ansi_test() {
+        let g = grid_2x3();
+        let r = Renderer::new(&g, |n| char::from(b'0' + n))
+            .layer(Layer::new([[0, 1].into(), [1, 1].into()]).bg(color::Blue))
+            .layer(Layer::new([[1, 1].into()]).fg(color::Red));
+
+        let mut out = Vec::new();
+        r.render(&mut out, true).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "123\n{blue}4{reset}{red}{blue}5{reset}6\n",
+                blue = color::Bg(color::Blue),
+                red = color::Fg(color::Red),
+                reset = style::Reset,
+            )
+        );
     }
 
     #[test]
@@ -373,13 +1307,13 @@ mod grid_tests {
         g.set_row(1, &[13, 11, 12]);
 
         #[rustfmt::skip]
-        assert_eq!(
-            g.cells,
-            vec![
-                vec![ 1,  2,  3],
-                vec![13, 11, 12],
-                vec![ 7,  8,  9]
-            ]
+        assert_grid_eq!(
+            g,
+            Grid::from([
+                [ 1,  2,  3],
+                [13, 11, 12],
+                [ 7,  8,  9]
+            ])
         );
     }
 }
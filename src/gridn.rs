@@ -0,0 +1,202 @@
+use crate::{grid::Grid, point::Point};
+
+/// A dense grid with any number of dimensions, indexed by [Point<D>].  The first axis (x) varies
+/// fastest in memory.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GridN<T: Copy, const D: usize> {
+    shape: [usize; D],
+    cells: Vec<T>,
+}
+
+impl<T: Copy, const D: usize> GridN<T, D> {
+    /// Create a grid with the given size along each axis, with every cell set to `fill`.
+    pub fn new(shape: [usize; D], fill: T) -> Self {
+        Self {
+            shape,
+            cells: vec![fill; shape.iter().product()],
+        }
+    }
+
+    /// Create a grid from cells listed with the x axis varying fastest.  Panics if the number of
+    /// cells doesn't match the shape.
+    pub fn from_vec(shape: [usize; D], cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), shape.iter().product::<usize>());
+        Self { shape, cells }
+    }
+
+    /// The size of the grid along each axis.
+    pub fn shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// The total number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Find the position of a point in the cell storage, if it's within the grid.
+    fn index(&self, p: Point<D>) -> Option<usize> {
+        let coords = <[usize; D]>::try_from(p).ok()?;
+        let mut index = 0;
+        for axis in (0..D).rev() {
+            if coords[axis] >= self.shape[axis] {
+                return None;
+            }
+            index = index * self.shape[axis] + coords[axis];
+        }
+        Some(index)
+    }
+
+    /// Find the point stored at the given position in the cell storage.
+    fn point(&self, mut index: usize) -> Point<D> {
+        let mut coords = [0; D];
+        for (axis, c) in coords.iter_mut().enumerate() {
+            *c = (index % self.shape[axis]) as i64;
+            index /= self.shape[axis];
+        }
+        coords.into()
+    }
+
+    pub fn get(&self, p: Point<D>) -> Option<T> {
+        self.index(p).map(|i| self.cells[i])
+    }
+
+    /// Set data in the grid.  Panics if the point is out of bounds.
+    pub fn set(&mut self, p: Point<D>, new_data: T) {
+        let i = self
+            .index(p)
+            .unwrap_or_else(|| panic!("{p} is outside the grid"));
+        self.cells[i] = new_data;
+    }
+
+    /// Iterate over every point in the grid with its data, x axis varying fastest.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<D>, T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point(i), *cell))
+    }
+
+    /// Get the in-bounds neighbors one step away along a single axis (up to 2·D of them).
+    pub fn neighbors_axis(&self, p: Point<D>) -> impl Iterator<Item = (Point<D>, T)> + '_ {
+        (0..D)
+            .flat_map(|axis| [-1, 1].map(|d| (axis, d)))
+            .filter_map(move |(axis, d)| {
+                let mut n = p;
+                n.coords[axis] += d;
+                self.get(n).map(|data| (n, data))
+            })
+    }
+
+    /// Get every in-bounds neighbor, including diagonals along any combination of axes (up to
+    /// 3^D − 1 of them).
+    pub fn neighbors_all(&self, p: Point<D>) -> impl Iterator<Item = (Point<D>, T)> + '_ {
+        (0..3usize.pow(D as u32)).filter_map(move |mut offset| {
+            let mut n = p;
+            for c in n.coords.iter_mut() {
+                *c += (offset % 3) as i64 - 1;
+                offset /= 3;
+            }
+            if n == p {
+                return None;
+            }
+            self.get(n).map(|data| (n, data))
+        })
+    }
+
+    /// Copy out the 2D plane spanned by `x_axis` and `y_axis` that passes through `at`.  The
+    /// other coordinates of `at` pick which plane; its coordinates along the two axes are
+    /// ignored.
+    pub fn slice(&self, x_axis: usize, y_axis: usize, at: Point<D>) -> Grid<T> {
+        assert!(x_axis < D && y_axis < D && x_axis != y_axis);
+        Grid::new(
+            (0..self.shape[y_axis])
+                .map(|y| {
+                    (0..self.shape[x_axis])
+                        .map(|x| {
+                            let mut p = at;
+                            p.coords[x_axis] = x as i64;
+                            p.coords[y_axis] = y as i64;
+                            self.get(p)
+                                .unwrap_or_else(|| panic!("{at} is outside the grid"))
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Lift a 2D grid into a GridN with the same shape.
+impl<T: Copy> From<&Grid<T>> for GridN<T, 2> {
+    fn from(grid: &Grid<T>) -> Self {
+        GridN::from_vec(
+            [grid.width(), grid.height()],
+            grid.cells.iter().flatten().copied().collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod gridn_tests {
+    use super::*;
+
+    fn cube() -> GridN<u8, 3> {
+        GridN::from_vec([3, 3, 3], (0..27).collect())
+    }
+
+    #[test]
+    fn get_set_test() {
+        let mut g = cube();
+        assert_eq!(g.get([0, 0, 0].into()), Some(0));
+        assert_eq!(g.get([1, 0, 0].into()), Some(1));
+        assert_eq!(g.get([0, 1, 0].into()), Some(3));
+        assert_eq!(g.get([0, 0, 1].into()), Some(9));
+        assert_eq!(g.get([2, 2, 2].into()), Some(26));
+        assert_eq!(g.get([3, 0, 0].into()), None);
+        assert_eq!(g.get([0, -1, 0].into()), None);
+
+        g.set([1, 2, 1].into(), 100);
+        assert_eq!(g.get([1, 2, 1].into()), Some(100));
+        assert_eq!(g.iter().find(|c| c.1 == 100).unwrap().0, [1, 2, 1].into());
+    }
+
+    #[test]
+    fn neighbors_test() {
+        let g = cube();
+        assert_eq!(g.neighbors_axis([1, 1, 1].into()).count(), 6);
+        assert_eq!(g.neighbors_axis([0, 0, 0].into()).count(), 3);
+        assert_eq!(g.neighbors_all([1, 1, 1].into()).count(), 26);
+        assert_eq!(g.neighbors_all([0, 0, 0].into()).count(), 7);
+
+        let g4: GridN<bool, 4> = GridN::new([3, 3, 3, 3], false);
+        assert_eq!(g4.neighbors_axis([1, 1, 1, 1].into()).count(), 8);
+        assert_eq!(g4.neighbors_all([1, 1, 1, 1].into()).count(), 80);
+    }
+
+    #[test]
+    fn slice_test() {
+        let g = cube();
+        assert_eq!(
+            g.slice(0, 1, [0, 0, 2].into()),
+            Grid::from([[18, 19, 20], [21, 22, 23], [24, 25, 26]])
+        );
+        assert_eq!(
+            g.slice(0, 2, [0, 1, 0].into()),
+            Grid::from([[3, 4, 5], [12, 13, 14], [21, 22, 23]])
+        );
+    }
+
+    #[test]
+    fn from_grid_test() {
+        let g = Grid::from([[1, 2, 3], [4, 5, 6]]);
+        let n = GridN::from(&g);
+        assert_eq!(n.shape(), [3, 2]);
+        assert_eq!(n.get([2, 1].into()), Some(6));
+        assert_eq!(n.slice(0, 1, [0, 0].into()), g);
+    }
+}
@@ -0,0 +1,395 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::{grid::Grid, point::Point, rect::Rect};
+
+/// A cell in a hex grid, in axial coordinates.  The third cube coordinate is implied, since
+/// `q + r + s == 0` always holds.  `q` increases to the east and `r` to the south, like x and y
+/// in a square grid.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, Ord, PartialOrd)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+/// The six neighboring offsets, counter-clockwise from east.
+const AXIAL_STEPS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+impl Hex {
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// Create a hex from cube coordinates.  Panics if they don't sum to zero.
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Self {
+        assert_eq!(q + r + s, 0, "cube coordinates must sum to zero");
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> [i64; 3] {
+        [self.q, self.r, self.s()]
+    }
+
+    /// The six hexes sharing an edge with this one.
+    pub fn neighbors(&self) -> [Hex; 6] {
+        AXIAL_STEPS.map(|step| *self + step)
+    }
+
+    /// The number of steps between two hexes.
+    pub fn distance(&self, other: &Hex) -> i64 {
+        let d = *self - *other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    /// The hexes exactly `radius` steps away, going around the ring.  A ring of radius zero is
+    /// just this hex.
+    pub fn ring(&self, radius: i64) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let mut hex = *self + AXIAL_STEPS[4] * radius;
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        for step in AXIAL_STEPS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex + step;
+            }
+        }
+        ring
+    }
+
+    /// Every hex at most `radius` steps away, including this one.
+    pub fn range(&self, radius: i64) -> impl Iterator<Item = Hex> + '_ {
+        (-radius..=radius).flat_map(move |dq| {
+            ((-radius).max(-dq - radius)..=radius.min(-dq + radius))
+                .map(move |dr| *self + Hex::new(dq, dr))
+        })
+    }
+
+    /// The square-grid position of the hex in an offset layout, where every other row (for
+    /// pointy-top hexes) or column (for flat-top hexes) is shoved half a hex over.  Odd rows or
+    /// columns are the ones shoved, matching the usual way hex maps are written as text.
+    pub fn to_offset(&self, orientation: Orientation) -> Point<2> {
+        match orientation {
+            Orientation::PointyTop => [self.q + (self.r - (self.r & 1)) / 2, self.r],
+            Orientation::FlatTop => [self.q, self.r + (self.q - (self.q & 1)) / 2],
+        }
+        .into()
+    }
+
+    /// The inverse of [Hex::to_offset].
+    pub fn from_offset(p: Point<2>, orientation: Orientation) -> Self {
+        match orientation {
+            Orientation::PointyTop => Hex::new(p.x() - (p.y() - (p.y() & 1)) / 2, p.y()),
+            Orientation::FlatTop => Hex::new(p.x(), p.y() - (p.x() - (p.x() & 1)) / 2),
+        }
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Self::Output {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+/// Which way the hexes in a grid point.  Flat-top hexes stack in columns, and pointy-top hexes
+/// in rows.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Orientation {
+    FlatTop,
+    PointyTop,
+}
+
+/// The directions between flat-top hexes, which have neighbors directly above and below.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum FlatDir {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl FlatDir {
+    /// All six directions, clockwise from N.
+    pub fn all() -> [FlatDir; 6] {
+        use FlatDir::*;
+        [N, NE, SE, S, SW, NW]
+    }
+
+    pub fn cw(&self) -> FlatDir {
+        let all = FlatDir::all();
+        all[(*self as usize + 1) % 6]
+    }
+
+    pub fn ccw(&self) -> FlatDir {
+        let all = FlatDir::all();
+        all[(*self as usize + 5) % 6]
+    }
+
+    /// The offset to the neighboring hex in this direction.
+    pub fn delta(&self) -> Hex {
+        use FlatDir::*;
+        match self {
+            N => Hex::new(0, -1),
+            NE => Hex::new(1, -1),
+            SE => Hex::new(1, 0),
+            S => Hex::new(0, 1),
+            SW => Hex::new(-1, 1),
+            NW => Hex::new(-1, 0),
+        }
+    }
+}
+
+/// The directions between pointy-top hexes, which have neighbors directly left and right.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PointyDir {
+    NE,
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+}
+
+impl PointyDir {
+    /// All six directions, clockwise from NE.
+    pub fn all() -> [PointyDir; 6] {
+        use PointyDir::*;
+        [NE, E, SE, SW, W, NW]
+    }
+
+    pub fn cw(&self) -> PointyDir {
+        let all = PointyDir::all();
+        all[(*self as usize + 1) % 6]
+    }
+
+    pub fn ccw(&self) -> PointyDir {
+        let all = PointyDir::all();
+        all[(*self as usize + 5) % 6]
+    }
+
+    /// The offset to the neighboring hex in this direction.
+    pub fn delta(&self) -> Hex {
+        use PointyDir::*;
+        match self {
+            NE => Hex::new(1, -1),
+            E => Hex::new(1, 0),
+            SE => Hex::new(0, 1),
+            SW => Hex::new(-1, 1),
+            W => Hex::new(-1, 0),
+            NW => Hex::new(0, -1),
+        }
+    }
+}
+
+impl From<FlatDir> for Hex {
+    fn from(dir: FlatDir) -> Self {
+        dir.delta()
+    }
+}
+
+impl From<PointyDir> for Hex {
+    fn from(dir: PointyDir) -> Self {
+        dir.delta()
+    }
+}
+
+/// Lay out hexes as text, one char per hex, with blanks between.  Pointy-top rows are staggered
+/// by one char, and flat-top columns by one line, so neighbors stay next to each other:
+///
+/// ```text
+/// pointy-top     flat-top
+/// a b c          a   c
+///  d e f           b
+///                d   f
+///                  e
+/// ```
+pub fn render(
+    cells: impl IntoIterator<Item = (Hex, char)>,
+    orientation: Orientation,
+) -> Grid<char> {
+    // doubled coordinates, where every hex lands on a distinct square
+    let cells: Vec<(Point<2>, char)> = cells
+        .into_iter()
+        .map(|(hex, c)| {
+            let p = match orientation {
+                Orientation::PointyTop => [2 * hex.q + hex.r, hex.r],
+                Orientation::FlatTop => [2 * hex.q, 2 * hex.r + hex.q],
+            };
+            (p.into(), c)
+        })
+        .collect();
+
+    let Some(bounds) = Rect::bounding(cells.iter().map(|(p, _)| *p)) else {
+        return Grid::new(vec![]);
+    };
+    let size = bounds.size();
+    let mut grid = Grid::new(vec![vec![' '; size.x() as usize]; size.y() as usize]);
+    for (p, c) in cells {
+        grid.setp(p - bounds.min, c);
+    }
+    grid
+}
+
+#[cfg(test)]
+mod hex_tests {
+    use super::*;
+
+    #[test]
+    fn cube_test() {
+        let h = Hex::from_cube(2, -3, 1);
+        assert_eq!(h, Hex::new(2, -3));
+        assert_eq!(h.cube(), [2, -3, 1]);
+        for n in h.neighbors() {
+            assert_eq!(n.cube().iter().sum::<i64>(), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn bad_cube_test() {
+        Hex::from_cube(1, 1, 1);
+    }
+
+    #[test]
+    fn directions_test() {
+        for dir in FlatDir::all() {
+            assert_eq!(dir.cw().ccw(), dir);
+            assert_eq!(dir.cw().cw().cw().delta(), -dir.delta());
+            assert_eq!(Hex::from(dir).distance(&Hex::new(0, 0)), 1);
+        }
+        for dir in PointyDir::all() {
+            assert_eq!(dir.cw().ccw(), dir);
+            assert_eq!(dir.cw().cw().cw().delta(), -dir.delta());
+        }
+
+        // both sets of directions reach the same six neighbors
+        let origin = Hex::new(0, 0);
+        let mut flat: Vec<Hex> = FlatDir::all().map(Hex::from).to_vec();
+        let mut pointy: Vec<Hex> = PointyDir::all().map(Hex::from).to_vec();
+        let mut neighbors = origin.neighbors().to_vec();
+        flat.sort();
+        pointy.sort();
+        neighbors.sort();
+        assert_eq!(flat, neighbors);
+        assert_eq!(pointy, neighbors);
+    }
+
+    #[test]
+    fn distance_test() {
+        let a = Hex::new(0, 0);
+        assert_eq!(a.distance(&Hex::new(3, -3)), 3);
+        assert_eq!(a.distance(&Hex::new(2, 1)), 3);
+        assert_eq!(a.distance(&Hex::new(-1, -1)), 2);
+
+        // walking in a straight line
+        let walked = (0..5).fold(a, |h, _| h + PointyDir::SE.delta());
+        assert_eq!(a.distance(&walked), 5);
+    }
+
+    #[test]
+    fn ring_test() {
+        let center = Hex::new(2, -1);
+        assert_eq!(center.ring(0), vec![center]);
+        for radius in 1..5 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len() as i64, 6 * radius);
+            assert!(ring.iter().all(|h| h.distance(&center) == radius));
+            // consecutive hexes are neighbors, all the way around
+            for (i, h) in ring.iter().enumerate() {
+                assert_eq!(h.distance(&ring[(i + 1) % ring.len()]), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn range_test() {
+        let center = Hex::new(-1, 3);
+        for radius in 0..5 {
+            let range: Vec<Hex> = center.range(radius).collect();
+            assert_eq!(range.len() as i64, 3 * radius * (radius + 1) + 1);
+            assert!(range.iter().all(|h| h.distance(&center) <= radius));
+        }
+    }
+
+    #[test]
+    fn offset_test() {
+        for orientation in [Orientation::FlatTop, Orientation::PointyTop] {
+            for h in Hex::new(0, 0).range(4) {
+                assert_eq!(Hex::from_offset(h.to_offset(orientation), orientation), h);
+            }
+        }
+
+        // in odd-r layout, the hex below-right of (0, 0) is directly below it
+        assert_eq!(
+            PointyDir::SE.delta().to_offset(Orientation::PointyTop),
+            [0, 1].into()
+        );
+        // and an odd row's below-right neighbor is one column over
+        let odd = Hex::from_offset([0, 1].into(), Orientation::PointyTop);
+        assert_eq!(
+            (odd + PointyDir::SE.delta()).to_offset(Orientation::PointyTop),
+            [1, 2].into()
+        );
+    }
+
+    #[test]
+    fn render_test() {
+        let rows = ["abc", "def"];
+        let cells = |orientation| {
+            rows.iter().enumerate().flat_map(move |(y, row)| {
+                row.chars().enumerate().map(move |(x, c)| {
+                    (Hex::from_offset([x, y].try_into().unwrap(), orientation), c)
+                })
+            })
+        };
+        assert_eq!(
+            render(cells(Orientation::PointyTop), Orientation::PointyTop).to_string(),
+            "a b c \n d e f\n"
+        );
+        assert_eq!(
+            render(cells(Orientation::FlatTop), Orientation::FlatTop).to_string(),
+            "a   c\n  b  \nd   f\n  e  \n"
+        );
+        assert_eq!(render([], Orientation::FlatTop).to_string(), "");
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+/// A run of positions, from `start` (inclusive) to `end` (exclusive).  A span with
+/// `end <= start` is empty.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, PartialOrd, Ord)]
+pub struct Span {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Span {
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        self.start <= n && n < self.end
+    }
+
+    /// Whether the spans share any positions.
+    pub fn overlaps(&self, other: &Span) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// The positions in both spans, or None if there aren't any.
+    pub fn intersect(&self, other: &Span) -> Option<Span> {
+        let s = Span::new(self.start.max(other.start), self.end.min(other.end));
+        (!s.is_empty()).then_some(s)
+    }
+
+    /// The sum of every position in the span, eg for a checksum that weights each position.
+    pub fn sum(&self) -> i64 {
+        (self.start + self.end - 1) * self.len() / 2
+    }
+
+    pub fn iter(&self) -> Range<i64> {
+        self.start..self.end
+    }
+}
+
+impl From<Range<i64>> for Span {
+    fn from(r: Range<i64>) -> Self {
+        Self::new(r.start, r.end)
+    }
+}
+
+/// A set of positions, stored as the spans they make up.  Touching or overlapping spans are
+/// merged as they're inserted, so the spans are always disjoint, in order, and have a gap
+/// between each.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct IntervalSet {
+    /// Each span's end, keyed by its start.
+    spans: BTreeMap<i64, i64>,
+    /// The starts of the spans of each length, for finding a fit without a scan.
+    by_len: BTreeMap<i64, BTreeSet<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of separate spans.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// The total number of positions in the set.
+    pub fn coverage(&self) -> i64 {
+        self.iter().map(|s| s.len()).sum()
+    }
+
+    /// The spans in the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Span> + '_ {
+        self.spans.iter().map(|(s, e)| Span::new(*s, *e))
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        self.span_at(n).is_some()
+    }
+
+    /// The span that includes the position, if any.
+    pub fn span_at(&self, n: i64) -> Option<Span> {
+        self.spans
+            .range(..=n)
+            .next_back()
+            .map(|(s, e)| Span::new(*s, *e))
+            .filter(|s| s.contains(n))
+    }
+
+    /// Add every position in the span, merging it with any spans it touches.
+    pub fn insert(&mut self, span: impl Into<Span>) {
+        let Span { mut start, mut end } = span.into();
+        if end <= start {
+            return;
+        }
+
+        if let Some((s, e)) = self.spans.range(..start).next_back() {
+            if *e >= start {
+                start = *s;
+            }
+        }
+        let merged: Vec<i64> = self.spans.range(start..=end).map(|(s, _)| *s).collect();
+        for s in merged {
+            end = end.max(self.take(s));
+        }
+        self.put(start, end);
+    }
+
+    /// Take every position in the span out of the set, splitting any span it falls inside.
+    pub fn remove(&mut self, span: impl Into<Span>) {
+        let span = span.into();
+        if span.is_empty() {
+            return;
+        }
+
+        // a span starting before the removed one keeps its left part
+        if let Some((s, e)) = self.spans.range(..span.start).next_back() {
+            let (s, e) = (*s, *e);
+            if e > span.start {
+                self.take(s);
+                self.put(s, span.start);
+                if e > span.end {
+                    self.put(span.end, e);
+                }
+            }
+        }
+        let cut: Vec<i64> = self
+            .spans
+            .range(span.start..span.end)
+            .map(|(s, _)| *s)
+            .collect();
+        for s in cut {
+            let e = self.take(s);
+            if e > span.end {
+                self.put(span.end, e);
+            }
+        }
+    }
+
+    /// Add a span that doesn't touch any other.
+    fn put(&mut self, start: i64, end: i64) {
+        self.spans.insert(start, end);
+        self.by_len.entry(end - start).or_default().insert(start);
+    }
+
+    /// Take out the span with the given start, returning its end.
+    fn take(&mut self, start: i64) -> i64 {
+        let end = self.spans.remove(&start).unwrap();
+        let starts = self.by_len.get_mut(&(end - start)).unwrap();
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.by_len.remove(&(end - start));
+        }
+        end
+    }
+
+    /// The parts of `within` that aren't in the set, in order.
+    pub fn gaps(&self, within: Span) -> Vec<Span> {
+        let mut gaps = Vec::new();
+        let mut cursor = within.start;
+        let first = self.span_at(within.start).map_or(within.start, |s| s.start);
+        for s in self
+            .spans
+            .range(first..within.end)
+            .map(|(s, e)| Span::new(*s, *e))
+        {
+            if s.start > cursor {
+                gaps.push(Span::new(cursor, s.start));
+            }
+            cursor = cursor.max(s.end);
+        }
+        if cursor < within.end {
+            gaps.push(Span::new(cursor, within.end));
+        }
+        gaps
+    }
+
+    /// The leftmost run of `len` positions in the set that ends at or before `before`, ie the
+    /// first fit for something of that length that has to move left.  Only looks at the first
+    /// span of each length that's long enough, so it's quick when there are few distinct
+    /// lengths, however many spans there are.  None if `len` isn't positive.
+    pub fn first_fit(&self, len: i64, before: i64) -> Option<Span> {
+        if len <= 0 {
+            return None;
+        }
+        self.by_len
+            .range(len..)
+            .filter_map(|(_, starts)| starts.first())
+            .min()
+            .filter(|start| *start + len <= before)
+            .map(|start| Span::new(*start, start + len))
+    }
+}
+
+impl<S: Into<Span>> FromIterator<S> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for span in iter {
+            set.insert(span);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod intervals_tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn span_test() {
+        let s = Span::from(2..6);
+        assert_eq!(s.len(), 4);
+        assert!(s.contains(2) && s.contains(5) && !s.contains(6));
+        assert!(Span::new(3, 3).is_empty());
+        assert_eq!(Span::new(5, 3).len(), 0);
+        assert_eq!(s.intersect(&(4..9).into()), Some(Span::new(4, 6)));
+        assert_eq!(s.intersect(&(6..9).into()), None);
+        assert!(!s.overlaps(&(0..2).into()));
+        assert_eq!(s.sum(), 2 + 3 + 4 + 5);
+        assert_eq!(Span::new(4, 4).sum(), 0);
+        assert_eq!(s.iter().collect::<Vec<_>>(), [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn insert_test() {
+        let mut set = IntervalSet::new();
+        set.insert(0..3);
+        set.insert(5..7);
+        assert_eq!(set.len(), 2);
+        // touching spans merge
+        set.insert(3..4);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [(0..4).into(), (5..7).into()]
+        );
+        // one span swallowing several
+        set.insert(2..10);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [Span::new(0, 10)]);
+        set.insert(4..6);
+        set.insert(7..7);
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.coverage(), 10);
+        assert!(set.contains(0) && set.contains(9) && !set.contains(10) && !set.contains(-1));
+    }
+
+    #[test]
+    fn remove_test() {
+        let mut set: IntervalSet = [0..10, 20..30].into_iter().collect();
+        // splitting a span
+        set.remove(3..5);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [(0..3).into(), (5..10).into(), (20..30).into()]
+        );
+        // trimming the ends of two spans, and dropping one between
+        set.remove(8..22);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [(0..3).into(), (5..8).into(), (22..30).into()]
+        );
+        set.remove(40..50);
+        set.remove(-5..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn gaps_test() {
+        let set: IntervalSet = [2..4, 6..8].into_iter().collect();
+        assert_eq!(
+            set.gaps((0..10).into()),
+            [(0..2).into(), (4..6).into(), (8..10).into()]
+        );
+        assert_eq!(set.gaps((3..7).into()), [Span::new(4, 6)]);
+        assert_eq!(set.gaps((6..8).into()), []);
+        assert_eq!(IntervalSet::new().gaps((1..3).into()), [Span::new(1, 3)]);
+    }
+
+    #[test]
+    fn first_fit_test() {
+        let set: IntervalSet = [2..3, 4..7, 10..20].into_iter().collect();
+        assert_eq!(set.first_fit(1, 20), Some((2..3).into()));
+        assert_eq!(set.first_fit(2, 20), Some((4..6).into()));
+        assert_eq!(set.first_fit(3, 7), Some((4..7).into()));
+        assert_eq!(set.first_fit(3, 6), None);
+        assert_eq!(set.first_fit(5, 15), Some((10..15).into()));
+        assert_eq!(set.first_fit(5, 14), None);
+        assert_eq!(set.first_fit(0, 10), None);
+        assert_eq!(set.first_fit(-1, 10), None);
+    }
+
+    #[test]
+    fn matches_set_of_positions_test() {
+        // random inserts and removes, checked against a plain set of positions
+        let mut rng = Rng::new(0x2024);
+
+        let mut set = IntervalSet::new();
+        let mut positions = BTreeSet::new();
+        for _ in 0..500 {
+            let start = rng.below(100);
+            let span = Span::new(start, start + rng.below(12));
+            if rng.below(3) == 0 {
+                set.remove(span);
+                span.iter().for_each(|n| {
+                    positions.remove(&n);
+                });
+            } else {
+                set.insert(span);
+                positions.extend(span.iter());
+            }
+
+            assert_eq!(set.coverage(), positions.len() as i64);
+            assert!((-1..112).all(|n| set.contains(n) == positions.contains(&n)));
+            // spans stay disjoint with gaps between them
+            assert!(set
+                .iter()
+                .zip(set.iter().skip(1))
+                .all(|(a, b)| !a.is_empty() && a.end < b.start));
+            // first_fit agrees with scanning every span
+            for len in 1..8 {
+                let before = rng.below(120);
+                let scan = set
+                    .iter()
+                    .find(|s| s.len() >= len && s.start + len <= before)
+                    .map(|s| Span::new(s.start, s.start + len));
+                assert_eq!(set.first_fit(len, before), scan);
+            }
+        }
+    }
+}
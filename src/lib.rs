@@ -1,5 +1,8 @@
 #![allow(clippy::let_and_return)]
 #![allow(unused)]
+pub mod bitgrid;
+pub mod clique;
+pub mod cycle;
 pub mod d1;
 pub mod d10;
 pub mod d11;
@@ -26,5 +29,21 @@ pub mod d7;
 pub mod d8;
 pub mod d9;
 pub mod direction;
+pub mod geometry;
+pub mod graph;
 pub mod grid;
+pub mod gridn;
+pub mod hex;
+pub mod intervals;
+pub mod linear;
+pub mod memo;
+pub mod num;
+pub mod ocr;
+pub mod parse;
 pub mod point;
+pub mod rect;
+#[cfg(test)]
+mod rng;
+pub mod sparse_grid;
+pub mod transform;
+pub mod wrapping_grid;
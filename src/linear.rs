@@ -0,0 +1,329 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::num::{ext_gcd, gcd};
+
+/// An exact fraction, kept in lowest terms with a positive denominator.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub struct Frac {
+    num: i128,
+    den: i128,
+}
+
+impl Frac {
+    /// Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert_ne!(den, 0, "fraction with a zero denominator");
+        let g = gcd(num, den) * den.signum();
+        Self {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn num(&self) -> i128 {
+        self.num
+    }
+
+    pub fn den(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+}
+
+impl From<i128> for Frac {
+    fn from(n: i128) -> Self {
+        Self { num: n, den: 1 }
+    }
+}
+
+impl Add for Frac {
+    type Output = Frac;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Frac::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Sub for Frac {
+    type Output = Frac;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for Frac {
+    type Output = Frac;
+
+    fn neg(self) -> Self::Output {
+        Frac {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Mul for Frac {
+    type Output = Frac;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Frac::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+/// Panics when dividing by zero.
+impl Div for Frac {
+    type Output = Frac;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Frac::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl Display for Frac {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// The solutions of a system of linear equations.  See [solve].
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Solution {
+    /// Exactly one solution, and every unknown is an integer.
+    Integer(Vec<i128>),
+    /// Exactly one solution, with at least one unknown that isn't an integer.
+    Rational(Vec<Frac>),
+    /// The equations contradict each other.
+    None,
+    /// Some unknowns can take any value, with the others following from them.
+    Infinite,
+}
+
+impl Solution {
+    /// The solution, if it's unique and every unknown is a non-negative integer, eg a number of
+    /// button presses.
+    pub fn non_negative_integer(&self) -> Option<&[i128]> {
+        match self {
+            Solution::Integer(xs) if xs.iter().all(|x| *x >= 0) => Some(xs),
+            _ => None,
+        }
+    }
+}
+
+/// Solve the linear equations `coeffs · x = rhs`, one row of coefficients per equation, by
+/// Gauss-Jordan elimination over exact fractions.  There can be any number of equations, but
+/// every row must have the same number of unknowns.
+pub fn solve(coeffs: &[Vec<i128>], rhs: &[i128]) -> Solution {
+    assert_eq!(coeffs.len(), rhs.len(), "one right-hand side per equation");
+    let n = coeffs.first().map_or(0, |row| row.len());
+
+    // the augmented matrix, with the right-hand side as the last column
+    let mut m: Vec<Vec<Frac>> = coeffs
+        .iter()
+        .zip(rhs)
+        .map(|(row, b)| {
+            assert_eq!(row.len(), n, "every equation needs the same unknowns");
+            row.iter().chain([b]).map(|c| Frac::from(*c)).collect()
+        })
+        .collect();
+
+    let mut rank = 0;
+    for col in 0..n {
+        let Some(pivot) = (rank..m.len()).find(|r| !m[*r][col].is_zero()) else {
+            continue;
+        };
+        m.swap(rank, pivot);
+
+        let p = m[rank][col];
+        for c in m[rank].iter_mut() {
+            *c = *c / p;
+        }
+        let pivot_row = m[rank].clone();
+        for (r, row) in m.iter_mut().enumerate().filter(|(r, _)| *r != rank) {
+            let factor = row[col];
+            if !factor.is_zero() {
+                for (c, p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *c = *c - factor * *p;
+                }
+            }
+        }
+        rank += 1;
+    }
+
+    // a leftover row with no unknowns says 0 = something
+    if m[rank..].iter().any(|row| !row[n].is_zero()) {
+        return Solution::None;
+    }
+    if rank < n {
+        return Solution::Infinite;
+    }
+
+    let xs: Vec<Frac> = m[..n].iter().map(|row| row[n]).collect();
+    match xs.iter().map(Frac::to_integer).collect() {
+        Some(ints) => Solution::Integer(ints),
+        None => Solution::Rational(xs),
+    }
+}
+
+/// The cheapest way to make `target` from non-negative whole numbers of `a` and `b`, ie the
+/// `(x, y)` with `x * a + y * b == target` and `x, y >= 0` that minimizes
+/// `x * cost.0 + y * cost.1`.  This is what's left of a two-unknown system when its equations
+/// are all multiples of one, so [solve] reports infinitely many solutions.  `a` and `b` must be
+/// positive.
+pub fn min_cost_non_negative(
+    a: i128,
+    b: i128,
+    target: i128,
+    cost: (i128, i128),
+) -> Option<(i128, i128)> {
+    assert!(a > 0 && b > 0, "step sizes must be positive");
+
+    // every solution is (x0 + k * b/g, y0 - k * a/g) for some integer k
+    let (g, x0, y0) = ext_gcd(a, b);
+    if target % g != 0 {
+        return None;
+    }
+    let (x0, y0) = (x0 * (target / g), y0 * (target / g));
+    let (dx, dy) = (b / g, a / g);
+
+    // x >= 0 and y >= 0 bound k from both sides
+    let k_min = (-x0).div_euclid(dx) + ((-x0).rem_euclid(dx) != 0) as i128;
+    let k_max = y0.div_euclid(dy);
+    if k_min > k_max {
+        return None;
+    }
+
+    // the cost changes by the same amount with each step of k, so the best is at an end
+    let k = if cost.0 * dx - cost.1 * dy > 0 {
+        k_min
+    } else {
+        k_max
+    };
+    Some((x0 + k * dx, y0 - k * dy))
+}
+
+#[cfg(test)]
+mod linear_tests {
+    use super::*;
+
+    #[test]
+    fn frac_test() {
+        let half = Frac::new(2, 4);
+        assert_eq!(half, Frac::new(-1, -2));
+        assert_eq!((half.num(), half.den()), (1, 2));
+        assert_eq!(Frac::new(3, -6), -half);
+        assert_eq!(half + Frac::new(1, 3), Frac::new(5, 6));
+        assert_eq!(half - half, Frac::from(0));
+        assert_eq!(half * Frac::from(4), Frac::from(2));
+        assert_eq!(Frac::from(3) / Frac::from(6), half);
+        assert_eq!(Frac::new(6, 3).to_integer(), Some(2));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!(Frac::new(-7, 2).to_string(), "-7/2");
+        assert_eq!(Frac::new(8, 2).to_string(), "4");
+    }
+
+    #[test]
+    fn unique_test() {
+        // the first claw machine from the d13 example: 80 presses of A and 40 of B
+        assert_eq!(
+            solve(&[vec![94, 22], vec![34, 67]], &[8400, 5400]),
+            Solution::Integer(vec![80, 40])
+        );
+        // and the second, which can't be won with whole presses
+        let s = solve(&[vec![26, 67], vec![66, 21]], &[12748, 12176]);
+        assert!(matches!(s, Solution::Rational(_)));
+        assert_eq!(s.non_negative_integer(), None);
+
+        // with the part 2 offset, the fourth machine becomes winnable
+        let offset = 10_000_000_000_000;
+        let s = solve(
+            &[vec![69, 27], vec![23, 71]],
+            &[18641 + offset, 10279 + offset],
+        );
+        assert_eq!(
+            s.non_negative_integer(),
+            Some([102851800151, 107526881786].as_slice())
+        );
+    }
+
+    #[test]
+    fn larger_system_test() {
+        // x + 2y - z = 3, 2x - y + 3z = 6, -x + y + 2z = 5, plus the sum of the first two
+        let coeffs = [
+            vec![1, 2, -1],
+            vec![2, -1, 3],
+            vec![-1, 1, 2],
+            vec![3, 1, 2],
+        ];
+        assert_eq!(
+            solve(&coeffs, &[3, 6, 5, 9]),
+            Solution::Integer(vec![1, 2, 2])
+        );
+        assert_eq!(solve(&coeffs, &[3, 6, 5, 10]), Solution::None);
+
+        // needs a row swap, since the first pivot is zero
+        assert_eq!(
+            solve(&[vec![0, 2], vec![3, 0]], &[1, 1]),
+            Solution::Rational(vec![Frac::new(1, 3), Frac::new(1, 2)])
+        );
+    }
+
+    #[test]
+    fn degenerate_test() {
+        // parallel buttons: every equation is a multiple of the first
+        assert_eq!(
+            solve(&[vec![2, 4], vec![3, 6]], &[10, 15]),
+            Solution::Infinite
+        );
+        assert_eq!(solve(&[vec![2, 4], vec![3, 6]], &[10, 16]), Solution::None);
+        assert_eq!(solve(&[vec![1, 1, 1]], &[3]), Solution::Infinite);
+        assert_eq!(solve(&[vec![0, 0]], &[0]), Solution::Infinite);
+    }
+
+    #[test]
+    fn min_cost_test() {
+        // with A costing 3 and B costing 1, prefer B
+        assert_eq!(min_cost_non_negative(2, 4, 10, (3, 1)), Some((1, 2)));
+        // with A cheaper per step, use as many as possible
+        assert_eq!(min_cost_non_negative(2, 4, 10, (1, 3)), Some((5, 0)));
+        assert_eq!(min_cost_non_negative(4, 6, 7, (3, 1)), None);
+        assert_eq!(min_cost_non_negative(5, 7, 3, (1, 1)), None);
+        assert_eq!(min_cost_non_negative(5, 7, 0, (3, 1)), Some((0, 0)));
+        // step sizes too big for an i64
+        let unit = 1i128 << 70;
+        assert_eq!(
+            min_cost_non_negative(unit, 3 * unit, 5 * unit, (3, 1)),
+            Some((2, 1))
+        );
+
+        // check against trying every combination
+        for (a, b) in [(3, 5), (4, 6), (7, 2), (1, 1)] {
+            for target in 0..60 {
+                for cost in [(3, 1), (1, 3), (2, 2)] {
+                    let brute = (0..=target / a)
+                        .flat_map(|x| (0..=target / b).map(move |y| (x, y)))
+                        .filter(|(x, y)| x * a + y * b == target)
+                        .min_by_key(|(x, y)| (x * cost.0 + y * cost.1, *x));
+                    let found = min_cost_non_negative(a, b, target, cost);
+                    let price = |p: Option<(i128, i128)>| p.map(|(x, y)| x * cost.0 + y * cost.1);
+                    assert_eq!(price(found), price(brute), "{a} {b} {target} {cost:?}");
+                }
+            }
+        }
+    }
+}
@@ -24,8 +24,8 @@ fn main() {
             }
         }
     } else if (1..=25).contains(&args.day) {
-        let input_file = if args.input.is_some() {
-            args.input.unwrap()
+        let input_file = if let Some(input) = args.input {
+            input
         } else if args.example {
             format!("./examples/d{}", args.day)
         } else {
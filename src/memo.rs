@@ -0,0 +1,267 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, BuildHasherDefault, Hash, Hasher},
+};
+
+/// A cache of the answers to a recursive function, eg the number of stones a stone turns into
+/// after some blinks.  Each memo owns its cache, so separate memos (and separate tests) never
+/// see each other's answers, and a memo can be sent to another thread like any other value.
+///
+/// ```
+/// # use aoc2024::memo::Memo;
+/// let mut memo = Memo::fast();
+/// let fib = |fib: &mut dyn FnMut(u64) -> u64, n: u64| match n {
+///     0 | 1 => n,
+///     _ => fib(n - 1) + fib(n - 2),
+/// };
+/// assert_eq!(memo.get(90, fib), 2880067194370816120);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V, S = RandomState> {
+    cache: HashMap<K, V, S>,
+}
+
+impl<K, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V> Memo<K, V, FxBuildHasher> {
+    /// A memo that hashes keys with [FxHasher], which is much quicker for the small keys these
+    /// puzzles use.
+    pub fn fast() -> Self {
+        Self::with_hasher(FxBuildHasher::default())
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> Memo<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            cache: HashMap::with_hasher(hasher),
+        }
+    }
+
+    /// The number of answers remembered.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forget every answer, eg before reusing the memo with a different function.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K, V, S> Memo<K, V, S>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    S: BuildHasher,
+{
+    /// The answer for `key`, computing it with `f` if it isn't remembered yet.  `f` is called
+    /// with a function to recurse through, which answers from the memo too, and the key.  The
+    /// memo remembers answers by key alone, so every call to `get` on one memo must pass the
+    /// same `f`.
+    pub fn get<F>(&mut self, key: K, f: F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        get(&mut self.cache, &f, key)
+    }
+
+    /// The answer for `key`, if it's been computed.
+    pub fn cached(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+}
+
+fn get<K, V, S, F>(cache: &mut HashMap<K, V, S>, f: &F, key: K) -> V
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    S: BuildHasher,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    if let Some(v) = cache.get(&key) {
+        return v.clone();
+    }
+    let v = f(&mut |k| get(cache, f, k), key.clone());
+    cache.insert(key, v.clone());
+    v
+}
+
+/// The hash used by rustc: a multiply and rotate per word.  It's fast, but isn't resistant to
+/// inputs chosen to collide, which puzzle inputs aren't.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+impl FxHasher {
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(Self::SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.add(n as u64);
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.add(n as u64);
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.add(n as u64);
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.add(n);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.add(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod memo_tests {
+    use std::{cell::Cell, collections::HashSet, thread};
+
+    use super::*;
+
+    fn fib(fib: &mut dyn FnMut(u64) -> u64, n: u64) -> u64 {
+        if n < 2 {
+            n
+        } else {
+            fib(n - 1) + fib(n - 2)
+        }
+    }
+
+    #[test]
+    fn recursion_test() {
+        let mut memo = Memo::new();
+        assert_eq!(memo.get(90, fib), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.cached(&50), Some(&12586269025));
+        assert_eq!(memo.cached(&91), None);
+
+        // each key is computed once
+        let calls = Cell::new(0);
+        let mut memo = Memo::fast();
+        let f = |rec: &mut dyn FnMut(u64) -> u64, n| {
+            calls.set(calls.get() + 1);
+            fib(rec, n)
+        };
+        memo.get(30, f);
+        memo.get(30, f);
+        memo.get(20, f);
+        assert_eq!(calls.get(), 31);
+
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    fn stones_test() {
+        // the d11 example: how many stones one stone becomes after some blinks
+        let blink = |count: &mut dyn FnMut((u64, u32)) -> u64, (stone, blinks): (u64, u32)| {
+            if blinks == 0 {
+                return 1;
+            }
+            let digits = crate::num::num_digits(stone, 10);
+            if stone == 0 {
+                count((1, blinks - 1))
+            } else if digits.is_multiple_of(2) {
+                let (a, b) = crate::num::split_digits(stone, digits / 2);
+                count((a, blinks - 1)) + count((b, blinks - 1))
+            } else {
+                count((stone * 2024, blinks - 1))
+            }
+        };
+        let mut memo = Memo::fast();
+        let mut total = |blinks| {
+            [125, 17]
+                .map(|s| memo.get((s, blinks), blink))
+                .iter()
+                .sum::<u64>()
+        };
+        assert_eq!(total(6), 22);
+        assert_eq!(total(25), 55312);
+        // far too many stones to count one by one
+        assert_eq!(total(75), 65601038650482);
+    }
+
+    #[test]
+    fn isolation_test() {
+        // memos on different threads, with different functions for the same keys
+        let handles: Vec<_> = (1..=4u64)
+            .map(|k| {
+                thread::spawn(move || {
+                    let mut memo = Memo::fast();
+                    memo.get(10, |rec: &mut dyn FnMut(u64) -> u64, n| match n {
+                        0 => k,
+                        _ => rec(n - 1) + k,
+                    })
+                })
+            })
+            .collect();
+        let answers: Vec<u64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(answers, [11, 22, 33, 44]);
+    }
+
+    #[test]
+    fn fx_hasher_test() {
+        let hash = |v: &dyn Fn(&mut FxHasher)| {
+            let mut h = FxHasher::default();
+            v(&mut h);
+            h.finish()
+        };
+        assert_eq!(hash(&|h| 42u64.hash(h)), hash(&|h| 42u64.hash(h)));
+        assert_ne!(hash(&|h| 1u64.hash(h)), hash(&|h| 2u64.hash(h)));
+        assert_ne!(hash(&|h| "ab".hash(h)), hash(&|h| "ba".hash(h)));
+        assert_ne!(
+            hash(&|h| h.write(b"abcdefghi")),
+            hash(&|h| h.write(b"abcdefgh"))
+        );
+
+        // small keys don't collide
+        let hashes: HashSet<u64> = (0..10_000u64)
+            .map(|n| FxBuildHasher::default().hash_one((n, n as u32)))
+            .collect();
+        assert_eq!(hashes.len(), 10_000);
+    }
+}
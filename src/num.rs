@@ -0,0 +1,253 @@
+use std::ops::Neg;
+
+use crate::point::Coord;
+
+/// The greatest common divisor of the magnitudes of a and b.
+pub fn gcd<T: Coord>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a.rem_euclid(b));
+    }
+    a
+}
+
+/// The least common multiple of the magnitudes of a and b, or 0 if either is 0.  Panics if it
+/// doesn't fit in `T`, rather than wrapping.
+pub fn lcm<T: Coord>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        T::ZERO
+    } else {
+        (a / gcd(a, b)).checked_mul(b).expect("lcm overflows").abs()
+    }
+}
+
+/// The gcd of all the numbers, or 0 if there are none.
+pub fn gcd_all<T: Coord>(ns: impl IntoIterator<Item = T>) -> T {
+    ns.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The lcm of all the numbers, eg the period of several cycles running together.  1 if there are
+/// none.  Panics if it doesn't fit in `T`.
+pub fn lcm_all<T: Coord>(ns: impl IntoIterator<Item = T>) -> T {
+    ns.into_iter().fold(T::ONE, lcm)
+}
+
+/// The extended Euclidean algorithm.  Returns `(g, x, y)` where `g` is the gcd of a and b, and
+/// `a * x + b * y == g`.
+pub fn ext_gcd<T: Coord + Neg<Output = T>>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The number `x` in `0..m` with `a * x ≡ 1 (mod m)`, or None if a and m aren't coprime.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base` to the power `exp`, modulo `m`, by repeated squaring.  The result is in `0..m`.
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let m = m as i128;
+    let mut base = (base as i128).rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as i64
+}
+
+/// The Chinese remainder theorem: find `x` with `x ≡ r (mod m)` for every `(r, m)` pair.
+/// Returns `(x, l)` where `l` is the lcm of the moduli and `x` is in `0..l`, so the solutions
+/// are exactly `x + k * l`.  The moduli don't have to be coprime, but then the residues have to
+/// agree wherever the moduli overlap; if they don't, there's no solution and this returns None.
+/// Also returns None if a modulus isn't positive, or if the lcm of the moduli doesn't fit in an
+/// i64.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences.into_iter().try_fold((0, 1), |(x, l), (r, m)| {
+        if m <= 0 {
+            return None;
+        }
+        // solve x + l * k ≡ r (mod m) for k
+        let (g, inv, _) = ext_gcd(l, m);
+        let diff = r - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let m_g = (m / g) as i128;
+        let k = ((diff / g) as i128 * inv as i128).rem_euclid(m_g);
+        let new_l = l as i128 * m_g;
+        let new_x = (x as i128 + l as i128 * k).rem_euclid(new_l);
+        Some((i64::try_from(new_x).ok()?, i64::try_from(new_l).ok()?))
+    })
+}
+
+/// The number of digits needed to write `n` in the given base.  Zero takes one digit.
+pub fn num_digits(n: u64, base: u64) -> u32 {
+    n.checked_ilog(base).map_or(1, |log| log + 1)
+}
+
+/// The digits of `n` in the given base, most significant first.
+pub fn digits(mut n: u64, base: u64) -> Vec<u64> {
+    let mut digits = vec![n % base];
+    n /= base;
+    while n > 0 {
+        digits.push(n % base);
+        n /= base;
+    }
+    digits.reverse();
+    digits
+}
+
+/// The number written by the decimal digits of `a` followed by the digits of `b`, eg 12 and 345
+/// make 12345.
+pub fn concat(a: u64, b: u64) -> u64 {
+    a * 10u64.pow(num_digits(b, 10)) + b
+}
+
+/// Split `n` into the number written by all but its last `at` decimal digits, and the number
+/// written by those last digits, eg 12345 split at 2 is (123, 45).
+pub fn split_digits(n: u64, at: u32) -> (u64, u64) {
+    let p = 10u64.pow(at);
+    (n / p, n % p)
+}
+
+#[cfg(test)]
+mod num_tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm_test() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all::<i64>([]), 0);
+        assert_eq!(lcm_all([2, 3, 4, 5]), 60);
+        assert_eq!(lcm_all::<i64>([]), 1);
+        assert_eq!(lcm_all([1i128 << 70, 3]), 3i128 << 70);
+        assert_eq!(gcd_all([12usize, 18]), 6);
+        assert_eq!(gcd(12i128 << 80, 18i128 << 70), 6i128 << 70);
+        assert_eq!(lcm(4usize, 6), 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm overflows")]
+    fn lcm_overflow_test() {
+        lcm_all([1_000_000_007i64, 1_000_000_009, 998_244_353]);
+    }
+
+    #[test]
+    fn ext_gcd_test() {
+        for a in -30..30 {
+            for b in -30..30 {
+                let (g, x, y) = ext_gcd(a, b);
+                assert_eq!(g, gcd(a, b));
+                assert_eq!(a * x + b * y, g);
+            }
+        }
+
+        // wider than an i64
+        let (a, b) = (3i128 << 80, 5i128 << 70);
+        let (g, x, y) = ext_gcd(a, b);
+        assert_eq!(g, 1i128 << 70);
+        assert_eq!(a * x + b * y, g);
+    }
+
+    #[test]
+    fn mod_inv_test() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(6, 9), None);
+        for m in 2..40 {
+            for a in 0..m {
+                match mod_inv(a, m) {
+                    Some(inv) => assert_eq!(a * inv % m, 1),
+                    None => assert_ne!(gcd(a, m), 1),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mod_pow_test() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 7), 6);
+        assert_eq!(mod_pow(5, 0, 7), 1);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        // Fermat's little theorem, with a big prime to exercise the wide multiply
+        let p = 1_000_000_007;
+        assert_eq!(mod_pow(123_456_789, p as u64 - 1, p), 1);
+        for e in 0..20 {
+            assert_eq!(mod_pow(3, e, 1_000), 3i64.pow(e as u32) % 1_000);
+        }
+    }
+
+    #[test]
+    fn crt_test() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli that share factors, with residues that agree
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        // and that disagree
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(-1, 101)]), Some((100, 101)));
+        // moduli that aren't positive
+        assert_eq!(crt([(1, 3), (2, 0)]), None);
+        assert_eq!(crt([(1, -3), (2, 5)]), None);
+
+        // large coprime moduli whose product only just fits in an i64
+        let (a, b) = (3_000_000_001, 3_000_000_003);
+        let (x, l) = crt([(1, a), (2, b)]).unwrap();
+        assert_eq!(l as i128, a as i128 * b as i128);
+        assert_eq!((x % a, x % b), (1, 2));
+        // and whose product doesn't
+        assert_eq!(crt([(1, 4_000_000_007), (2, 4_000_000_009)]), None);
+
+        // every solution matches a brute-force search
+        for (a, b) in [(4, 6), (9, 12), (5, 7), (8, 8)] {
+            for ra in 0..a {
+                for rb in 0..b {
+                    let brute = (0..lcm(a, b)).find(|x| x % a == ra && x % b == rb);
+                    assert_eq!(crt([(ra, a), (rb, b)]).map(|(x, _)| x), brute);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn digits_test() {
+        assert_eq!(num_digits(0, 10), 1);
+        assert_eq!(num_digits(9, 10), 1);
+        assert_eq!(num_digits(10, 10), 2);
+        assert_eq!(num_digits(u64::MAX, 10), 20);
+        assert_eq!(num_digits(255, 2), 8);
+        assert_eq!(digits(1203, 10), [1, 2, 0, 3]);
+        assert_eq!(digits(0, 10), [0]);
+        assert_eq!(digits(6, 2), [1, 1, 0]);
+        assert_eq!(concat(12, 345), 12345);
+        assert_eq!(concat(15, 6), 156);
+        assert_eq!(concat(7, 0), 70);
+        assert_eq!(split_digits(12345, 2), (123, 45));
+        assert_eq!(split_digits(1000, 2), (10, 0));
+    }
+}
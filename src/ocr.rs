@@ -0,0 +1,211 @@
+use crate::{bitgrid::BitGrid, grid::Grid};
+
+/// The 6-high letters drawn by most puzzles that render text (for example 2016 day 8, 2019 days
+/// 8 and 11, 2021 day 13, and 2022 day 10).  Letters start every 5 columns; most are 4 wide with
+/// a blank column after, but Y fills all 5.
+#[rustfmt::skip]
+const SMALL_FONT: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The 6x10 letters drawn by 2018 day 10.  Letters start every 8 columns, leaving two blank
+/// columns between them.
+#[rustfmt::skip]
+const LARGE_FONT: [(char, [&str; 10]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Read the letters drawn in a grid, where true cells are lit.  The font (4x6 or 6x10) is chosen
+/// by the height of the lit area, and letters are read at the font's fixed pitch.  Returns None
+/// if nothing is lit or any letter isn't recognized.
+pub fn recognize(grid: &Grid<bool>) -> Option<String> {
+    let lit = |x: usize, y: usize| grid.get(x, y) == Some(true);
+
+    let rows: Vec<usize> = (0..grid.height())
+        .filter(|y| (0..grid.width()).any(|x| lit(x, *y)))
+        .collect();
+    let (top, bottom) = (*rows.first()?, *rows.last()?);
+    let cols: Vec<usize> = (0..grid.width())
+        .filter(|x| (top..=bottom).any(|y| lit(*x, y)))
+        .collect();
+    let (left, right) = (*cols.first()?, *cols.last()?);
+
+    let pitch = match bottom - top + 1 {
+        6 => 5,
+        10 => 8,
+        _ => return None,
+    };
+
+    let glyph_at = |x_start: usize| -> Vec<String> {
+        (top..=bottom)
+            .map(|y| {
+                (x_start..x_start + pitch)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    };
+
+    let read = |x_start: usize| -> Option<String> {
+        (x_start..=right)
+            .step_by(pitch)
+            .map(|x| {
+                let glyph = glyph_at(x);
+                match pitch {
+                    5 => lookup(&SMALL_FONT, &glyph),
+                    _ => lookup(&LARGE_FONT, &glyph),
+                }
+            })
+            .collect()
+    };
+
+    // a text starting with a letter whose first column is blank, like I, starts a column
+    // before the first lit one
+    read(left).or_else(|| read(left.checked_sub(1)?))
+}
+
+/// The letter drawn by a glyph, which is as wide as the font's pitch.
+fn lookup<const H: usize>(font: &[(char, [&str; H])], glyph: &[String]) -> Option<char> {
+    font.iter()
+        .find(|(_, rows)| {
+            rows.iter()
+                .zip(glyph)
+                .all(|(a, b)| format!("{a:.<width$}", width = b.len()) == *b)
+        })
+        .map(|(c, _)| *c)
+}
+
+/// Read the letters drawn in a bit grid.  See [recognize].
+pub fn recognize_bits(bits: &BitGrid) -> Option<String> {
+    recognize(&bits.to_grid())
+}
+
+#[cfg(test)]
+mod ocr_tests {
+    use super::*;
+
+    /// Build a grid from lines of `#` (lit) and `.` (unlit).
+    fn picture(lines: &[&str]) -> Grid<bool> {
+        Grid::new(
+            lines
+                .iter()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect(),
+        )
+    }
+
+    /// Draw text with a font, starting a letter every `pitch` columns.
+    fn draw<const H: usize>(text: &str, font: &[(char, [&str; H])], pitch: usize) -> Grid<bool> {
+        let lines: Vec<String> = (0..H)
+            .map(|y| {
+                text.chars()
+                    .map(|c| {
+                        let row = font.iter().find(|g| g.0 == c).unwrap().1[y];
+                        format!("{row:.<pitch$}")
+                    })
+                    .collect()
+            })
+            .collect();
+        picture(&lines.iter().map(|l| l.as_str()).collect::<Vec<&str>>())
+    }
+
+    #[test]
+    fn small_font_test() {
+        #[rustfmt::skip]
+        let g = picture(&[
+            "..........................",
+            ".#..#.####.#....#.....##..",
+            ".#..#.#....#....#....#..#.",
+            ".####.###..#....#....#..#.",
+            ".#..#.#....#....#....#..#.",
+            ".#..#.#....#....#....#..#.",
+            ".#..#.####.####.####..##..",
+            "..........................",
+        ]);
+        assert_eq!(recognize(&g), Some("HELLO".to_string()));
+        assert_eq!(
+            recognize_bits(&BitGrid::from(&g)),
+            Some("HELLO".to_string())
+        );
+    }
+
+    #[test]
+    fn fonts_round_trip_test() {
+        let small: String = SMALL_FONT.iter().map(|g| g.0).collect();
+        assert_eq!(recognize(&draw(&small, &SMALL_FONT, 5)), Some(small));
+
+        let large: String = LARGE_FONT.iter().map(|g| g.0).collect();
+        assert_eq!(recognize(&draw(&large, &LARGE_FONT, 8)), Some(large));
+
+        // extra padding around the text doesn't matter
+        let g = draw("ZERO", &SMALL_FONT, 5).pad(false, 2);
+        assert_eq!(recognize(&g), Some("ZERO".to_string()));
+    }
+
+    #[test]
+    fn i_and_y_test() {
+        #[rustfmt::skip]
+        let g = picture(&[
+            ".###.#...#.##..",
+            "..#..#...##..#.",
+            "..#...#.#.#..#.",
+            "..#....#..#..#.",
+            "..#....#..#..#.",
+            ".###...#...##..",
+        ]);
+        assert_eq!(recognize(&g), Some("IYO".to_string()));
+
+        // starting with I, whose first column is blank, even with no margin to the left
+        let g = draw("ICY", &SMALL_FONT, 5);
+        assert_eq!(recognize(&g), Some("ICY".to_string()));
+        assert_eq!(
+            recognize(&draw("YIKES", &SMALL_FONT, 5)),
+            Some("YIKES".to_string())
+        );
+        assert_eq!(
+            recognize(&draw("HIYA", &SMALL_FONT, 5).pad(false, 1)),
+            Some("HIYA".to_string())
+        );
+    }
+
+    #[test]
+    fn unrecognized_test() {
+        assert_eq!(recognize(&picture(&["....", "...."])), None);
+        assert_eq!(recognize(&picture(&["###", "#.#", "###"])), None);
+
+        let mut g = draw("AB", &SMALL_FONT, 5);
+        g.set(0, 0, true);
+        assert_eq!(recognize(&g), None);
+    }
+}
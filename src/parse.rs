@@ -0,0 +1,276 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// A problem with the puzzle input, and where it is.  Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub col: usize,
+    pub msg: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.col, self.msg)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of input, or part of one, that knows where it came from so errors can point at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub text: &'a str,
+    /// The line number, counting from 1.
+    pub line: usize,
+    /// The column where `text` starts, counting from 1.
+    pub col: usize,
+}
+
+/// The lines of the input, numbered.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        text,
+        line: i + 1,
+        col: 1,
+    })
+}
+
+/// The lines of the input, numbered and grouped into sections that are separated by blank lines.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![vec![]];
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            sections.push(vec![]);
+        } else {
+            sections.last_mut().unwrap().push(line);
+        }
+    }
+    sections.retain(|s| !s.is_empty());
+    sections
+}
+
+impl<'a> Line<'a> {
+    /// A line with no known position in the input, which is treated as line 1.
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            line: 1,
+            col: 1,
+        }
+    }
+
+    /// An error pointing at the given byte offset into the text.
+    pub fn error(&self, at: usize, msg: impl Display) -> ParseError {
+        ParseError {
+            line: self.line,
+            col: self.col + self.text[..at].chars().count(),
+            msg: msg.to_string(),
+        }
+    }
+
+    /// The part of the text between two byte offsets.
+    fn slice(&self, start: usize, end: usize) -> Line<'a> {
+        Line {
+            text: &self.text[start..end],
+            line: self.line,
+            col: self.col + self.text[..start].chars().count(),
+        }
+    }
+
+    /// The text with surrounding whitespace removed.
+    pub fn trim(&self) -> Line<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        let end = self.text.trim_end().len().max(start);
+        self.slice(start, end)
+    }
+
+    /// Parse the whole text (ignoring surrounding whitespace) as one value.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let t = self.trim();
+        t.text
+            .parse()
+            .map_err(|e| t.error(0, format!("{:?}: {e}", t.text)))
+    }
+
+    /// Split the text at the first `sep`, eg a `key: value` line.
+    pub fn split_once(&self, sep: &str) -> Result<(Line<'a>, Line<'a>), ParseError> {
+        let i = self
+            .text
+            .find(sep)
+            .ok_or_else(|| self.error(0, format!("expected {sep:?}")))?;
+        Ok((self.slice(0, i), self.slice(i + sep.len(), self.text.len())))
+    }
+
+    /// Parse both sides of the first `sep`, eg `key: value` or `47|53`.
+    pub fn pair<A, B>(&self, sep: &str) -> Result<(A, B), ParseError>
+    where
+        A: FromStr,
+        A::Err: Display,
+        B: FromStr,
+        B::Err: Display,
+    {
+        let (a, b) = self.split_once(sep)?;
+        Ok((a.parse()?, b.parse()?))
+    }
+
+    /// Parse each item between `delim`s, eg `75,47,61`.
+    pub fn list<T>(&self, delim: &str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let mut start = 0;
+        let mut items = Vec::new();
+        for (i, _) in self.text.match_indices(delim) {
+            items.push(self.slice(start, i).parse()?);
+            start = i + delim.len();
+        }
+        items.push(self.slice(start, self.text.len()).parse()?);
+        Ok(items)
+    }
+
+    /// Parse each item separated by whitespace, eg `7 6 4 2 1`.
+    pub fn words<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split_whitespace()
+            .map(|word| {
+                let start = word.as_ptr() as usize - self.text.as_ptr() as usize;
+                self.slice(start, start + word.len()).parse()
+            })
+            .collect()
+    }
+
+    /// Every integer in the text, ignoring anything else around them, eg `p=0,4 v=3,-3` gives
+    /// 0, 4, 3 and -3.  A `-` directly before a number makes it negative, unless it follows a
+    /// digit, so a range like `1-3` gives 1 and 3.
+    pub fn ints<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut ints = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let negative = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if !negative && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            ints.push(self.slice(start, i).parse()?);
+        }
+        Ok(ints)
+    }
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+
+    #[test]
+    fn ints_test() {
+        let line = Line::new("p=0,4 v=3,-3 x-7 --2");
+        assert_eq!(line.ints::<i32>(), Ok(vec![0, 4, 3, -3, -7, -2]));
+        assert_eq!(Line::new("no numbers").ints::<i32>(), Ok(vec![]));
+        assert_eq!(Line::new("1-3 a").ints::<u32>(), Ok(vec![1, 3]));
+        assert_eq!(Line::new("-1--3").ints::<i32>(), Ok(vec![-1, -3]));
+        assert_eq!(
+            Line::new("Button A: X+94, Y+34").ints::<u64>(),
+            Ok(vec![94, 34])
+        );
+
+        let err = line.ints::<u32>().unwrap_err();
+        assert_eq!((err.line, err.col), (1, 11));
+        let err = Line::new("a 300").ints::<u8>().unwrap_err();
+        assert_eq!(err.col, 3);
+    }
+
+    #[test]
+    fn sections_test() {
+        let input = "47|53\n97|13\n\n75,47,61\n\n\n97,61\n";
+        let sections = sections(input);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].len(), 2);
+        assert_eq!(sections[1][0].text, "75,47,61");
+        assert_eq!(sections[1][0].line, 4);
+        assert_eq!(sections[2][0].line, 7);
+        assert!(super::sections("").is_empty());
+    }
+
+    #[test]
+    fn pair_test() {
+        assert_eq!(Line::new("47|53").pair("|"), Ok((47, 53)));
+        assert_eq!(Line::new("3   4").pair("   "), Ok((3, 4)));
+        assert_eq!(
+            Line::new("name: 12").pair::<String, u8>(": "),
+            Ok(("name".to_string(), 12))
+        );
+
+        let err = Line::new("4753").pair::<u32, u32>("|").unwrap_err();
+        assert_eq!(err.col, 1);
+        assert!(err.msg.contains("\"|\""));
+
+        // errors point at the bad side
+        let err = Line::new("47|x3").pair::<u32, u32>("|").unwrap_err();
+        assert_eq!(err.col, 4);
+    }
+
+    #[test]
+    fn list_test() {
+        let line = Line::new("75,47,61");
+        assert_eq!(line.list(","), Ok(vec![75, 47, 61]));
+        assert_eq!(Line::new("1, 2 , 3").list(","), Ok(vec![1, 2, 3]));
+        assert_eq!(Line::new("7 6  4").words(), Ok(vec![7, 6, 4]));
+
+        let err = Line::new("75,4x,61").list::<u32>(",").unwrap_err();
+        assert_eq!(err.col, 4);
+        let err = Line::new("7  6 y 2").words::<u32>().unwrap_err();
+        assert_eq!(err.col, 6);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 6: \"y\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn nested_position_test() {
+        // positions stay relative to the whole input through each split
+        let input = "190: 10 19\n3267: 81 40 27\n83: 17 x\n";
+        let errors: Vec<ParseError> = lines(input)
+            .filter_map(|line| {
+                let (_, terms) = line.split_once(": ").ok()?;
+                terms.words::<u64>().err()
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![ParseError {
+                line: 3,
+                col: 8,
+                msg: "\"x\": invalid digit found in string".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn unicode_column_test() {
+        // columns count chars, not bytes
+        let err = Line::new("→ 1 é 300").ints::<u8>().unwrap_err();
+        assert_eq!(err.col, 7);
+    }
+}
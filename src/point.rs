@@ -1,20 +1,119 @@
 use std::{
-    fmt::Display,
-    ops::{Add, Neg, Sub},
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
 };
 
 use crate::{
     direction::{CardDir, CardOrdDir},
     grid::Grid,
+    rect::Rect,
+    transform::Transform,
 };
 
+/// An integer type usable as a [Point] coordinate.
+pub trait Coord:
+    Copy
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn abs_diff(self, other: Self) -> Self;
+    fn rem_euclid(self, m: Self) -> Self;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                self.abs()
+            }
+            fn signum(self) -> Self {
+                self.signum()
+            }
+            fn abs_diff(self, other: Self) -> Self {
+                (self - other).abs()
+            }
+            fn rem_euclid(self, m: Self) -> Self {
+                self.rem_euclid(m)
+            }
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.checked_add(rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.checked_sub(rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                self.checked_mul(rhs)
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+impl_coord!(i32, i64, i128);
+
+impl Coord for usize {
+    const ZERO: Self = 0;
+    const ONE: Self = 1;
+
+    fn abs(self) -> Self {
+        self
+    }
+    fn signum(self) -> Self {
+        self.min(1)
+    }
+    fn abs_diff(self, other: Self) -> Self {
+        self.abs_diff(other)
+    }
+    fn rem_euclid(self, m: Self) -> Self {
+        self % m
+    }
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs)
+    }
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_sub(rhs)
+    }
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(rhs)
+    }
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+/// A point in `D` dimensions.  Coordinates are `i64` unless another [Coord] type is chosen, eg
+/// `i128` for big-number puzzles or `i32` to halve the memory of a large collection of points.
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Hash, Ord, PartialOrd)]
-pub struct Point<const D: usize> {
-    pub coords: [i64; D],
+pub struct Point<const D: usize, T: Coord = i64> {
+    pub coords: [T; D],
 }
 
-impl<const D: usize> Sub for Point<D> {
-    type Output = Point<D>;
+impl<const D: usize, T: Coord> Sub for Point<D, T> {
+    type Output = Point<D, T>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         let mut new_coords = self.coords;
@@ -25,8 +124,8 @@ impl<const D: usize> Sub for Point<D> {
     }
 }
 
-impl<const D: usize> Add for Point<D> {
-    type Output = Point<D>;
+impl<const D: usize, T: Coord> Add for Point<D, T> {
+    type Output = Point<D, T>;
 
     fn add(self, rhs: Self) -> Self::Output {
         let mut new_coords = self.coords;
@@ -37,166 +136,260 @@ impl<const D: usize> Add for Point<D> {
     }
 }
 
-impl<const D: usize> Neg for Point<D> {
-    type Output = Point<D>;
+impl<const D: usize, T: Coord + Neg<Output = T>> Neg for Point<D, T> {
+    type Output = Point<D, T>;
 
     fn neg(self) -> Self::Output {
-        let mut new_coords = self.coords;
-        for (i, c) in new_coords.iter_mut().enumerate() {
-            *c *= -1;
-        }
-        new_coords.into()
+        self.coords.map(|c| -c).into()
+    }
+}
+
+impl<const D: usize, T: Coord> AddAssign for Point<D, T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const D: usize, T: Coord> SubAssign for Point<D, T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Scale every coordinate by a scalar.
+impl<const D: usize, T: Coord> Mul<T> for Point<D, T> {
+    type Output = Point<D, T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.coords.map(|c| c * rhs).into()
     }
 }
 
-impl<const D: usize> Point<D> {
-    pub fn new(coords: &[i64; D]) -> Self {
+/// Divide every coordinate by a scalar, rounding towards zero.
+impl<const D: usize, T: Coord> Div<T> for Point<D, T> {
+    type Output = Point<D, T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        self.coords.map(|c| c / rhs).into()
+    }
+}
+
+impl<const D: usize, T: Coord> Point<D, T> {
+    pub fn new(coords: &[T; D]) -> Self {
         Self { coords: *coords }
     }
 
-    pub fn x(&self) -> i64 {
+    pub fn x(&self) -> T {
         self.coords[0]
     }
-    pub fn y(&self) -> i64 {
+    pub fn y(&self) -> T {
         self.coords[1]
     }
-    pub fn z(&self) -> i64 {
+    pub fn z(&self) -> T {
         self.coords[2]
     }
 
     /// Set a new value for the x coordinate.
-    pub fn set_x(&mut self, new_x: i64) {
+    pub fn set_x(&mut self, new_x: T) {
         self.coords[0] = new_x;
     }
 
     /// Set a new value for the y coordinate.
-    pub fn set_y(&mut self, new_y: i64) {
+    pub fn set_y(&mut self, new_y: T) {
         self.coords[1] = new_y;
     }
 
     /// Set a new value for the z coordinate.
-    pub fn set_z(&mut self, new_z: i64) {
+    pub fn set_z(&mut self, new_z: T) {
         self.coords[2] = new_z;
     }
 
-    /// Get the magnitude of the point, considered as a vector.
-    pub fn mag(&self) -> i64 {
-        self.coords.iter().product::<i64>() / self.coords.len() as i64
+    /// Convert to another coordinate type, or None if any coordinate doesn't fit in it.
+    pub fn try_cast<U: Coord + TryFrom<T>>(&self) -> Option<Point<D, U>> {
+        let mut coords = [U::ZERO; D];
+        for (i, c) in coords.iter_mut().enumerate() {
+            *c = U::try_from(self.coords[i]).ok()?;
+        }
+        Some(coords.into())
     }
 
-    /// Attempt to move the point one unit in the given direction (no diagonals), within a grid
-    /// bounds.  Returns None if the move would push the point outside the bounds of the grid.
-    pub fn move_in_grid<T: Copy>(&self, dir: CardDir, grid: &Grid<T>) -> Option<Point<D>> {
-        let mut p = *self;
-
-        match dir {
-            CardDir::Up => p.set_y(p.y().checked_sub(1)?),
-            CardDir::Down => p.set_y(p.y().checked_add(1)?),
-            CardDir::Left => p.set_x(p.x().checked_sub(1)?),
-            CardDir::Right => p.set_x(p.x().checked_add(1)?),
-        }
+    /// Get the magnitude (Euclidean length) of the point, considered as a vector.
+    pub fn mag(&self) -> f64 {
+        self.dot(self).to_f64().sqrt()
+    }
 
-        if p.x() > 0 && grid.width() as i64 > p.x() && p.y() > 0 && grid.height() as i64 > p.y() {
-            Some(p)
-        } else {
-            None
+    /// Combine each coordinate with the matching coordinate of another point.
+    fn zip_with(self, other: Point<D, T>, f: impl Fn(T, T) -> T) -> Point<D, T> {
+        let mut new_coords = self.coords;
+        for (i, c) in new_coords.iter_mut().enumerate() {
+            *c = f(*c, other.coords[i]);
         }
+        new_coords.into()
+    }
+
+    /// Wrap each coordinate into the range `0..m` for the matching coordinate of `m`, eg to keep
+    /// a point inside a grid whose edges wrap around.
+    pub fn rem_euclid(&self, m: Point<D, T>) -> Point<D, T> {
+        self.zip_with(m, T::rem_euclid)
+    }
+
+    /// The dot product of two points, considered as vectors.
+    pub fn dot(&self, other: &Point<D, T>) -> T {
+        self.coords
+            .iter()
+            .zip(other.coords)
+            .fold(T::ZERO, |sum, (a, b)| sum + *a * b)
+    }
+
+    /// The taxicab distance between two points: the sum of the distances along each axis.
+    pub fn manhattan(&self, other: &Point<D, T>) -> T {
+        self.zip_with(*other, T::abs_diff)
+            .coords
+            .into_iter()
+            .fold(T::ZERO, |sum, c| sum + c)
+    }
+
+    /// The chessboard distance between two points: the largest distance along any axis.
+    pub fn chebyshev(&self, other: &Point<D, T>) -> T {
+        self.zip_with(*other, T::abs_diff)
+            .coords
+            .into_iter()
+            .max()
+            .unwrap_or(T::ZERO)
+    }
+
+    /// The square of the straight-line distance between two points.  Stays exact, unlike the
+    /// distance itself.
+    pub fn dist_sq(&self, other: &Point<D, T>) -> T {
+        let d = self.zip_with(*other, T::abs_diff);
+        d.dot(&d)
+    }
+
+    /// The absolute value of each coordinate.
+    pub fn abs(&self) -> Point<D, T> {
+        self.coords.map(T::abs).into()
+    }
+
+    /// The sign (-1, 0, or 1) of each coordinate.  Turns a vector along an axis or diagonal
+    /// into a single step in the same direction.
+    pub fn signum(&self) -> Point<D, T> {
+        self.coords.map(T::signum).into()
+    }
+
+    /// The smaller of each pair of coordinates.  Unlike [Ord::min], which compares the points
+    /// lexicographically, this can give a point that is neither of the two.
+    pub fn component_min(self, other: Point<D, T>) -> Point<D, T> {
+        self.zip_with(other, T::min)
+    }
+
+    /// The larger of each pair of coordinates.  Unlike [Ord::max], which compares the points
+    /// lexicographically, this can give a point that is neither of the two.
+    pub fn component_max(self, other: Point<D, T>) -> Point<D, T> {
+        self.zip_with(other, T::max)
+    }
+}
+
+impl Point<2> {
+    /// Rotate 90° clockwise about the origin, on screen where y points down.  See [Transform] for
+    /// rotating about other points, or in 3D.
+    pub fn rotate_cw(&self) -> Point<2> {
+        Transform::rotate_cw().apply(*self)
+    }
+
+    /// Rotate 90° counter-clockwise about the origin, on screen where y points down.
+    pub fn rotate_ccw(&self) -> Point<2> {
+        Transform::rotate_ccw().apply(*self)
+    }
+
+    /// Attempt to move the point one unit in the given direction (no diagonals), within a grid
+    /// bounds.  Returns None if the move would push the point outside the bounds of the grid.
+    pub fn move_in_grid<T: Copy>(&self, dir: CardDir, grid: &Grid<T>) -> Option<Point<2>> {
+        let p = *self + dir.delta();
+        Rect::from(grid).contains(&p).then_some(p)
     }
 
     /// Attempt to move the point one unit in the given direction (diagonals allowed), within a
     /// grid bounds.  Returns None if the move would push the point outside the bounds of the grid.
-    pub fn move_in_grid_diag<T: Copy>(&self, dir: CardOrdDir, grid: &Grid<T>) -> Option<Point<D>> {
-        let mut p = *self;
-        match dir {
-            CardOrdDir::Up => {
-                p.set_y(p.y().checked_sub(1)?);
-            }
-            CardOrdDir::Down => {
-                p.set_y(p.y().checked_add(1)?);
-            }
-            CardOrdDir::Left => {
-                p.set_x(p.x().checked_sub(1)?);
-            }
-            CardOrdDir::Right => {
-                p.set_x(p.x().checked_add(1)?);
-            }
-            CardOrdDir::UpLeft => {
-                p.set_x(p.x().checked_sub(1)?);
-                p.set_y(p.y().checked_sub(1)?);
-            }
-            CardOrdDir::UpRight => {
-                p.set_x(p.x().checked_add(1)?);
-                p.set_y(p.y().checked_sub(1)?);
-            }
-            CardOrdDir::DownLeft => {
-                p.set_x(p.x().checked_sub(1)?);
-                p.set_y(p.y().checked_add(1)?);
-            }
-            CardOrdDir::DownRight => {
-                p.set_x(p.x().checked_add(1)?);
-                p.set_y(p.y().checked_add(1)?);
-            }
-        }
-
-        if p.x() > 0 && grid.width() as i64 > p.x() && p.y() > 0 && grid.height() as i64 > p.y() {
-            Some(p)
-        } else {
-            None
-        }
+    pub fn move_in_grid_diag<T: Copy>(&self, dir: CardOrdDir, grid: &Grid<T>) -> Option<Point<2>> {
+        let p = *self + dir.delta();
+        Rect::from(grid).contains(&p).then_some(p)
     }
 }
 
 // Make possible the nice pattern `&[1,2,3].into()` to create a Point.
+impl<const D: usize, T: Coord> From<&[T; D]> for Point<D, T> {
+    fn from(coords: &[T; D]) -> Self {
+        Point { coords: *coords }
+    }
+}
+
+// Make possible the nice pattern `[1,2,3].into()` to create a Point.
+impl<const D: usize, T: Coord> From<[T; D]> for Point<D, T> {
+    fn from(coords: [T; D]) -> Self {
+        Point { coords }
+    }
+}
+
+// Let the default `i64` points be built from `i32` literals and `usize` indices too.  The
+// conversion is checked, and panics rather than wrapping if a coordinate doesn't fit.
 impl<const D: usize> From<&[i32; D]> for Point<D> {
     fn from(coords: &[i32; D]) -> Self {
         Point {
-            coords: coords.map(|n| n as i64),
+            coords: coords.map(i64::from),
         }
     }
 }
 
-// Make possible the nice pattern `[1,2,3].into()` to create a Point.
 impl<const D: usize> From<[i32; D]> for Point<D> {
     fn from(coords: [i32; D]) -> Self {
-        Point {
-            coords: coords.map(|n| n as i64),
-        }
+        (&coords).into()
     }
 }
 
-// Make possible the nice pattern `&[1,2,3].into()` to create a Point.
-impl<const D: usize> From<&[i64; D]> for Point<D> {
-    fn from(coords: &[i64; D]) -> Self {
-        Point { coords: *coords }
+/// A point had a coordinate which didn't fit in the requested integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoordOutOfRange;
+
+impl Display for CoordOutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "coordinate out of range")
     }
 }
 
-// Make possible the nice pattern `[1,2,3].into()` to create a Point.
-impl<const D: usize> From<[i64; D]> for Point<D> {
-    fn from(coords: [i64; D]) -> Self {
-        Point { coords }
+impl Error for CoordOutOfRange {}
+
+/// Convert a point to array indices, failing if any coordinate is negative or too large.
+impl<const D: usize, T: Coord> TryFrom<Point<D, T>> for [usize; D]
+where
+    usize: TryFrom<T>,
+{
+    type Error = CoordOutOfRange;
+
+    fn try_from(p: Point<D, T>) -> Result<Self, Self::Error> {
+        p.try_cast::<usize>()
+            .map(|p| p.coords)
+            .ok_or(CoordOutOfRange)
     }
 }
 
-// Make possible the nice pattern `&[1,2,3].into()` to create a Point.
-impl<const D: usize> From<&[usize; D]> for Point<D> {
-    fn from(coords: &[usize; D]) -> Self {
-        Point {
-            coords: coords.map(|n| n as i64),
-        }
+/// Convert array indices to a point, failing if any index is too large for an i64.
+impl<const D: usize> TryFrom<&[usize; D]> for Point<D> {
+    type Error = CoordOutOfRange;
+
+    fn try_from(coords: &[usize; D]) -> Result<Self, Self::Error> {
+        Point::from(*coords).try_cast().ok_or(CoordOutOfRange)
     }
 }
 
-// Make possible the nice pattern `[1,2,3].into()` to create a Point.
-impl<const D: usize> From<[usize; D]> for Point<D> {
-    fn from(coords: [usize; D]) -> Self {
-        Point {
-            coords: coords.map(|n| n as i64),
-        }
+impl<const D: usize> TryFrom<[usize; D]> for Point<D> {
+    type Error = CoordOutOfRange;
+
+    fn try_from(coords: [usize; D]) -> Result<Self, Self::Error> {
+        (&coords).try_into()
     }
 }
 
-impl<const D: usize> Display for Point<D> {
+impl<const D: usize, T: Coord> Display for Point<D, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(")?;
         for (i, n) in self.coords.iter().enumerate() {
@@ -208,3 +401,185 @@ impl<const D: usize> Display for Point<D> {
         write!(f, ")")
     }
 }
+
+#[cfg(test)]
+mod point_tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    /// A deterministic spread of points with small coordinates, for checking properties that
+    /// should hold for any points.
+    fn samples<const D: usize>() -> Vec<Point<D>> {
+        let mut rng = Rng::new(0x2024);
+        (0..64)
+            .map(|_| {
+                let mut coords = [0; D];
+                for c in coords.iter_mut() {
+                    *c = rng.below(100) - 50;
+                }
+                coords.into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn add_sub_algebra_test() {
+        let zero = Point::<3>::new(&[0, 0, 0]);
+        for a in samples::<3>() {
+            assert_eq!(a + zero, a);
+            assert_eq!(a - a, zero);
+            assert_eq!(a + -a, zero);
+            for b in samples::<3>().into_iter().take(8) {
+                assert_eq!(a + b, b + a);
+                assert_eq!(a - b, -(b - a));
+                assert_eq!(a + b - b, a);
+
+                let mut c = a;
+                c += b;
+                assert_eq!(c, a + b);
+                c -= b;
+                assert_eq!(c, a);
+            }
+        }
+    }
+
+    #[test]
+    fn scalar_algebra_test() {
+        for a in samples::<2>() {
+            for b in samples::<2>().into_iter().take(8) {
+                for k in [-3, -1, 0, 1, 2, 7] {
+                    assert_eq!((a + b) * k, a * k + b * k);
+                    if k != 0 {
+                        assert_eq!((a * k) / k, a);
+                    }
+                }
+            }
+            assert_eq!(a * -1, -a);
+            assert_eq!(a * 2, a + a);
+        }
+    }
+
+    #[test]
+    fn rem_euclid_test() {
+        let size = Point::new(&[101, 103]);
+        for a in samples::<2>() {
+            let w = (a * 37).rem_euclid(size);
+            assert!((0..101).contains(&w.x()));
+            assert!((0..103).contains(&w.y()));
+            assert_eq!(w.rem_euclid(size), w);
+            assert_eq!((w + size * 3).rem_euclid(size), w);
+        }
+        assert_eq!(
+            Point::new(&[-1, 104]).rem_euclid(size),
+            Point::new(&[100, 1])
+        );
+    }
+
+    #[test]
+    fn distance_test() {
+        let origin = Point::<3>::new(&[0, 0, 0]);
+        for a in samples::<3>() {
+            assert_eq!(a.dot(&a), a.dist_sq(&origin));
+            assert_eq!(a.abs().coords.map(|c| c * c).iter().sum::<i64>(), a.dot(&a));
+            assert_eq!(a.mag(), (a.dot(&a) as f64).sqrt());
+            for b in samples::<3>().into_iter().take(8) {
+                assert_eq!(a.dot(&b), b.dot(&a));
+                assert_eq!(a.manhattan(&b), b.manhattan(&a));
+                assert!(a.chebyshev(&b) <= a.manhattan(&b));
+                assert!(a.manhattan(&b) <= 3 * a.chebyshev(&b));
+                assert!(a.manhattan(&b) <= a.manhattan(&origin) + origin.manhattan(&b));
+                assert_eq!(a.dist_sq(&b), (a - b).dot(&(a - b)));
+            }
+        }
+
+        let a = Point::new(&[1, -2]);
+        let b = Point::new(&[4, 2]);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.dist_sq(&b), 25);
+        assert_eq!((b - a).mag(), 5.0);
+    }
+
+    #[test]
+    fn abs_signum_min_max_test() {
+        for a in samples::<4>() {
+            let mut product = a.abs();
+            for (i, c) in product.coords.iter_mut().enumerate() {
+                *c *= a.signum().coords[i];
+            }
+            assert_eq!(product, a);
+            assert!(a.abs().coords.iter().all(|c| *c >= 0));
+
+            for b in samples::<4>().into_iter().take(8) {
+                assert_eq!(a.component_min(b) + a.component_max(b), a + b);
+                assert_eq!(a.component_min(b), b.component_min(a));
+                assert_eq!(a.component_max(b).component_max(a), a.component_max(b));
+            }
+        }
+
+        assert_eq!(
+            Point::new(&[3, -7]).component_min(Point::new(&[5, -9])),
+            Point::new(&[3, -9])
+        );
+        // Ord::min still compares lexicographically
+        assert_eq!(
+            Point::new(&[3, -7]).min(Point::new(&[5, -9])),
+            Point::new(&[3, -7])
+        );
+        assert_eq!(Point::new(&[0, -7, 4]).signum(), Point::new(&[0, -1, 1]));
+    }
+
+    #[test]
+    fn coord_types_test() {
+        let a: Point<2, i32> = [3, -4].into();
+        assert_eq!(a.mag(), 5.0);
+        assert_eq!(-a, Point::new(&[-3, 4]));
+
+        let big: Point<3, i128> = [i64::MAX as i128 * 4, 1, -1].into();
+        assert_eq!(big * 2 / 2, big);
+        assert_eq!(big.try_cast::<i64>(), None);
+
+        let u: Point<2, usize> = [2, 7].into();
+        let v: Point<2, usize> = [5, 3].into();
+        assert_eq!(u.manhattan(&v), 7);
+        assert_eq!(u.chebyshev(&v), 4);
+        assert_eq!(u.dist_sq(&v), 25);
+        assert_eq!(u.component_min(v), [2, 3].into());
+    }
+
+    #[test]
+    fn checked_conversion_test() {
+        let p: Point<2> = [3usize, 4].try_into().unwrap();
+        assert_eq!(p, Point::new(&[3, 4]));
+        assert_eq!(p.try_cast::<usize>(), Some(Point::new(&[3, 4])));
+        assert_eq!(<[usize; 2]>::try_from(p), Ok([3, 4]));
+
+        let negative = Point::new(&[3, -1]);
+        assert_eq!(negative.try_cast::<usize>(), None);
+        assert_eq!(<[usize; 2]>::try_from(negative), Err(CoordOutOfRange));
+        assert_eq!(Point::new(&[i64::MAX, 0]).try_cast::<i32>(), None);
+    }
+
+    #[test]
+    fn usize_overflow_test() {
+        assert_eq!(Point::<1>::try_from([usize::MAX]), Err(CoordOutOfRange));
+        assert_eq!(Point::<2>::try_from(&[1usize, 2]), Ok(Point::new(&[1, 2])));
+    }
+
+    #[test]
+    fn move_in_grid_test() {
+        let g = Grid::from([[0; 3]; 2]);
+        let p = Point::new(&[1, 1]);
+        assert_eq!(p.move_in_grid(CardDir::Up, &g), Some(Point::new(&[1, 0])));
+        assert_eq!(p.move_in_grid(CardDir::Left, &g), Some(Point::new(&[0, 1])));
+        assert_eq!(p.move_in_grid(CardDir::Down, &g), None);
+        assert_eq!(
+            p.move_in_grid_diag(CardOrdDir::UpLeft, &g),
+            Some(Point::new(&[0, 0]))
+        );
+        assert_eq!(
+            Point::new(&[0, 0]).move_in_grid_diag(CardOrdDir::UpRight, &g),
+            None
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{grid::Grid, point::Point};
+
+/// An axis-aligned box of points, from `min` (inclusive) to `max` (exclusive) on every axis.  A
+/// box with `max <= min` on any axis is empty.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub struct Rect<const D: usize> {
+    pub min: Point<D>,
+    pub max: Point<D>,
+}
+
+impl<const D: usize> Rect<D> {
+    pub fn new(min: impl Into<Point<D>>, max: impl Into<Point<D>>) -> Self {
+        Self {
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+
+    /// A box with one corner at the origin, eg the bounds of a grid with the given size.
+    pub fn from_size(size: impl Into<Point<D>>) -> Self {
+        Self::new([0i64; D], size)
+    }
+
+    /// The smallest box containing every point, or None if there are no points.
+    pub fn bounding(points: impl IntoIterator<Item = Point<D>>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds: Option<Self>, p| {
+            let one = Self::new(p, p + [1i64; D].into());
+            Some(bounds.map_or(one, |b| b.union(&one)))
+        })
+    }
+
+    /// The length of the box along each axis.
+    pub fn size(&self) -> Point<D> {
+        (self.max - self.min).component_max([0i64; D].into())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..D).any(|axis| self.max.coords[axis] <= self.min.coords[axis])
+    }
+
+    /// The number of points in the box (its volume, in more than two dimensions).
+    pub fn area(&self) -> i64 {
+        self.size().coords.iter().product()
+    }
+
+    pub fn contains(&self, p: &Point<D>) -> bool {
+        (0..D).all(|axis| (self.min.coords[axis]..self.max.coords[axis]).contains(&p.coords[axis]))
+    }
+
+    /// The box covered by both boxes, or None if they don't overlap.
+    pub fn intersect(&self, other: &Rect<D>) -> Option<Rect<D>> {
+        let overlap = Self::new(
+            self.min.component_max(other.min),
+            self.max.component_min(other.max),
+        );
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// The smallest box containing both boxes.  Empty boxes are ignored.
+    pub fn union(&self, other: &Rect<D>) -> Rect<D> {
+        if self.is_empty() {
+            *other
+        } else if other.is_empty() {
+            *self
+        } else {
+            Self::new(
+                self.min.component_min(other.min),
+                self.max.component_max(other.max),
+            )
+        }
+    }
+
+    /// Iterate over every point in the box, with the first axis changing fastest (for two
+    /// dimensions, that's reading order).
+    pub fn points(&self) -> impl Iterator<Item = Point<D>> {
+        let rect = *self;
+        let mut next = (!rect.is_empty()).then_some(rect.min);
+
+        std::iter::from_fn(move || {
+            let cur = next?;
+            let mut p = cur;
+            next = None;
+            for axis in 0..D {
+                p.coords[axis] += 1;
+                if p.coords[axis] < rect.max.coords[axis] {
+                    next = Some(p);
+                    break;
+                }
+                p.coords[axis] = rect.min.coords[axis];
+            }
+            Some(cur)
+        })
+    }
+}
+
+impl Rect<2> {
+    /// Split the box into its four quadrants: top-left, top-right, bottom-left, bottom-right.
+    /// When a side has odd length, the middle row or column belongs to no quadrant.
+    pub fn quadrants(&self) -> [Rect<2>; 4] {
+        let half = self.size() / 2;
+        let (x0, x1, x2, x3) = (
+            self.min.x(),
+            self.min.x() + half.x(),
+            self.max.x() - half.x(),
+            self.max.x(),
+        );
+        let (y0, y1, y2, y3) = (
+            self.min.y(),
+            self.min.y() + half.y(),
+            self.max.y() - half.y(),
+            self.max.y(),
+        );
+        [
+            Rect::new([x0, y0], [x1, y1]),
+            Rect::new([x2, y0], [x3, y1]),
+            Rect::new([x0, y2], [x1, y3]),
+            Rect::new([x2, y2], [x3, y3]),
+        ]
+    }
+}
+
+/// The bounds of a grid.
+impl<T: Copy> From<&Grid<T>> for Rect<2> {
+    fn from(grid: &Grid<T>) -> Self {
+        Rect::from_size(Point::try_from([grid.width(), grid.height()]).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod rect_tests {
+    use super::*;
+
+    #[test]
+    fn contains_test() {
+        let r = Rect::new([1, 2], [4, 5]);
+        assert!(r.contains(&[1, 2].into()));
+        assert!(r.contains(&[3, 4].into()));
+        assert!(!r.contains(&[4, 4].into()));
+        assert!(!r.contains(&[3, 5].into()));
+        assert!(!r.contains(&[0, 3].into()));
+        assert_eq!(r.area(), 9);
+        assert_eq!(r.size(), [3, 3].into());
+    }
+
+    #[test]
+    fn empty_test() {
+        let r = Rect::new([2, 2], [2, 5]);
+        assert!(r.is_empty());
+        assert_eq!(r.area(), 0);
+        assert_eq!(r.points().count(), 0);
+        assert!(!r.contains(&[2, 3].into()));
+        assert_eq!(Rect::new([5, 5], [1, 1]).area(), 0);
+    }
+
+    #[test]
+    fn intersect_union_test() {
+        let a = Rect::new([0, 0], [4, 3]);
+        let b = Rect::new([2, 1], [6, 6]);
+        assert_eq!(a.intersect(&b), Some(Rect::new([2, 1], [4, 3])));
+        assert_eq!(a.union(&b), Rect::new([0, 0], [6, 6]));
+        assert_eq!(a.intersect(&Rect::new([4, 0], [5, 3])), None);
+        assert_eq!(a.union(&Rect::new([9, 9], [9, 9])), a);
+    }
+
+    #[test]
+    fn points_test() {
+        let r = Rect::new([1, 1], [3, 3]);
+        let points: Vec<Point<2>> = r.points().collect();
+        assert_eq!(
+            points,
+            vec![[1, 1].into(), [2, 1].into(), [1, 2].into(), [2, 2].into()]
+        );
+
+        let cube = Rect::<3>::from_size([2, 3, 4]);
+        assert_eq!(cube.points().count() as i64, cube.area());
+        assert!(cube.points().all(|p| cube.contains(&p)));
+    }
+
+    #[test]
+    fn bounding_test() {
+        assert_eq!(Rect::<2>::bounding([]), None);
+        let points: [Point<2>; 3] = [[3, -1].into(), [0, 4].into(), [2, 2].into()];
+        let r = Rect::bounding(points).unwrap();
+        assert_eq!(r, Rect::new([0, -1], [4, 5]));
+        assert!(points.iter().all(|p| r.contains(p)));
+
+        let g = Grid::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(Rect::from(&g), Rect::new([0, 0], [3, 2]));
+    }
+
+    #[test]
+    fn quadrants_test() {
+        // odd sides leave out the middle row and column
+        let [tl, tr, bl, br] = Rect::from_size([11, 7]).quadrants();
+        assert_eq!(tl, Rect::new([0, 0], [5, 3]));
+        assert_eq!(tr, Rect::new([6, 0], [11, 3]));
+        assert_eq!(bl, Rect::new([0, 4], [5, 7]));
+        assert_eq!(br, Rect::new([6, 4], [11, 7]));
+        assert!(!Rect::from_size([11, 7])
+            .quadrants()
+            .iter()
+            .any(|q| q.contains(&[5, 2].into()) || q.contains(&[1, 3].into())));
+
+        // even sides split cleanly
+        let quadrants = Rect::new([2, 2], [6, 4]).quadrants();
+        assert_eq!(quadrants.iter().map(Rect::area).sum::<i64>(), 8);
+        assert_eq!(quadrants[3], Rect::new([4, 3], [6, 4]));
+    }
+}
@@ -0,0 +1,23 @@
+//! A small deterministic random number generator for tests that check properties over many
+//! inputs, so failures always reproduce.
+
+/// A linear congruential generator, returning the high bits of its state.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    seed: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// The next number, in `0..n`.
+    pub fn below(&mut self, n: u64) -> i64 {
+        self.seed = self
+            .seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.seed >> 33) % n) as i64
+    }
+}
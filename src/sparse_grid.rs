@@ -0,0 +1,215 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    grid::{Grid, GridLike},
+    point::Point,
+};
+
+/// A grid that only stores the cells that have been set, for puzzles whose coordinates are
+/// unbounded or mostly empty.  The bounds grow (and shrink) to fit whatever cells are present.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SparseGrid<T: Copy> {
+    pub cells: HashMap<Point<2>, T>,
+    /// The smallest and largest coordinates of any cell, or None if the grid is empty.
+    bounds: Option<(Point<2>, Point<2>)>,
+}
+
+impl<T: Copy> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    /// The top-left corner of the grid's bounds.
+    pub fn min(&self) -> Option<Point<2>> {
+        self.bounds.map(|b| b.0)
+    }
+
+    /// The bottom-right corner of the grid's bounds (inclusive).
+    pub fn max(&self) -> Option<Point<2>> {
+        self.bounds.map(|b| b.1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn getp(&self, p: Point<2>) -> Option<T> {
+        self.cells.get(&p).copied()
+    }
+
+    /// Set data in the grid, growing the bounds if needed.
+    pub fn setp(&mut self, p: Point<2>, new_data: T) {
+        self.cells.insert(p, new_data);
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                [min.x().min(p.x()), min.y().min(p.y())].into(),
+                [max.x().max(p.x()), max.y().max(p.y())].into(),
+            ),
+            None => (p, p),
+        });
+    }
+
+    /// Remove a cell from the grid, shrinking the bounds if it was on the edge.
+    pub fn remove(&mut self, p: Point<2>) -> Option<T> {
+        let removed = self.cells.remove(&p)?;
+
+        if let Some((min, max)) = self.bounds {
+            if p.x() == min.x() || p.y() == min.y() || p.x() == max.x() || p.y() == max.y() {
+                self.recompute_bounds();
+            }
+        }
+
+        Some(removed)
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = self.cells.keys().fold(None, |bounds, p| {
+            Some(match bounds {
+                Some((min, max)) => (
+                    [min.x().min(p.x()), min.y().min(p.y())].into(),
+                    [max.x().max(p.x()), max.y().max(p.y())].into(),
+                ),
+                None => (*p, *p),
+            })
+        });
+    }
+
+    /// Convert to a dense grid covering the current bounds, using `fill` for missing cells.  The
+    /// grid's top-left corner will be at the sparse grid's min().
+    pub fn to_grid(&self, fill: T) -> Grid<T> {
+        let Some((min, max)) = self.bounds else {
+            return Grid::new(vec![]);
+        };
+
+        Grid::new(
+            (min.y()..=max.y())
+                .map(|y| {
+                    (min.x()..=max.x())
+                        .map(|x| self.getp([x, y].into()).unwrap_or(fill))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<T: Copy> GridLike<T> for SparseGrid<T> {
+    fn width(&self) -> usize {
+        self.bounds
+            .map(|(min, max)| (max.x() - min.x() + 1) as usize)
+            .unwrap_or(0)
+    }
+
+    fn height(&self) -> usize {
+        self.bounds
+            .map(|(min, max)| (max.y() - min.y() + 1) as usize)
+            .unwrap_or(0)
+    }
+
+    fn getp(&self, p: Point<2>) -> Option<T> {
+        SparseGrid::getp(self, p)
+    }
+
+    fn setp(&mut self, p: Point<2>, new_data: T) {
+        SparseGrid::setp(self, p, new_data)
+    }
+}
+
+/// Copy every cell of a dense grid into a sparse one.
+impl<T: Copy> From<&Grid<T>> for SparseGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        let mut sparse = SparseGrid::new();
+        for (y, row) in grid.cells.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                sparse.setp([x, y].try_into().unwrap(), *cell);
+            }
+        }
+        sparse
+    }
+}
+
+/// Cells that haven't been set are printed as `.`.
+impl<T> Display for SparseGrid<T>
+where
+    T: Display + Copy,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((min, max)) = self.bounds {
+            for y in min.y()..=max.y() {
+                for x in min.x()..=max.x() {
+                    match self.getp([x, y].into()) {
+                        Some(cell) => write!(f, "{cell}")?,
+                        None => write!(f, ".")?,
+                    }
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod sparse_grid_tests {
+    use super::*;
+    use crate::grid::Cell;
+
+    #[test]
+    fn sparse_bounds_test() {
+        let mut g: SparseGrid<char> = SparseGrid::new();
+        assert_eq!(g.width(), 0);
+        assert_eq!(g.min(), None);
+
+        g.setp([2, 3].into(), 'a');
+        g.setp([-1, 5].into(), 'b');
+        g.setp([0, -2].into(), 'c');
+
+        assert_eq!(g.min(), Some([-1, -2].into()));
+        assert_eq!(g.max(), Some([2, 5].into()));
+        assert_eq!(g.width(), 4);
+        assert_eq!(g.height(), 8);
+
+        assert_eq!(g.remove([0, -2].into()), Some('c'));
+        assert_eq!(g.min(), Some([-1, 3].into()));
+        assert_eq!(g.height(), 3);
+
+        // removing an empty point on the edge leaves the bounds alone
+        assert_eq!(g.remove([2, 4].into()), None);
+        assert_eq!(g.max(), Some([2, 5].into()));
+    }
+
+    #[test]
+    fn sparse_display_test() {
+        let mut g: SparseGrid<char> = SparseGrid::new();
+        g.setp([-1, -1].into(), '#');
+        g.setp([1, 0].into(), '#');
+
+        assert_eq!(format!("{g}"), "#..\n..#\n");
+        assert_eq!(format!("{}", g.to_grid('.')), "#..\n..#\n");
+    }
+
+    #[test]
+    fn sparse_adj_test() {
+        let mut g: SparseGrid<u8> = SparseGrid::new();
+        g.setp([-5, -5].into(), 1);
+        g.setp([-5, -4].into(), 2);
+
+        let adj = g.adj_4([-5, -5].into());
+        assert_eq!(adj.down(), Some(Cell::new([-5, -4].into(), 2)));
+        assert_eq!(adj.up(), None);
+        assert_eq!(adj.left(), None);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::point::Point;
+
+/// A linear transform of D-dimensional integer space, stored as a matrix that multiplies points
+/// as column vectors.  Rotations by 90° and reflections across axes are the useful ones, and
+/// those all have a single ±1 in each row and column.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Hash)]
+pub struct Transform<const D: usize> {
+    pub m: [[i64; D]; D],
+}
+
+impl<const D: usize> Transform<D> {
+    pub fn identity() -> Self {
+        let mut m = [[0; D]; D];
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] = 1;
+        }
+        Self { m }
+    }
+
+    /// Reflect across the plane (or line, in 2D) perpendicular to the given axis, ie negate that
+    /// coordinate.
+    pub fn reflect(axis: usize) -> Self {
+        let mut t = Self::identity();
+        t.m[axis][axis] = -1;
+        t
+    }
+
+    pub fn apply(&self, p: Point<D>) -> Point<D> {
+        let mut coords = [0; D];
+        for (i, c) in coords.iter_mut().enumerate() {
+            *c = (0..D).map(|j| self.m[i][j] * p.coords[j]).sum();
+        }
+        coords.into()
+    }
+
+    /// Apply the transform with `pivot` as the origin, eg to rotate about a point other than
+    /// the origin.
+    pub fn apply_about(&self, p: Point<D>, pivot: Point<D>) -> Point<D> {
+        pivot + self.apply(p - pivot)
+    }
+
+    /// The transform that applies `first`, then this one.
+    pub fn compose(&self, first: &Transform<D>) -> Transform<D> {
+        let mut m = [[0; D]; D];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                *c = (0..D).map(|k| self.m[i][k] * first.m[k][j]).sum();
+            }
+        }
+        Transform { m }
+    }
+
+    pub fn transpose(&self) -> Transform<D> {
+        let mut m = [[0; D]; D];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                *c = self.m[j][i];
+            }
+        }
+        Transform { m }
+    }
+
+    /// The transform that undoes this one.  Only rotations and reflections are supported (their
+    /// inverse is their transpose); anything that stretches or skews space gives None.
+    pub fn inverse(&self) -> Option<Transform<D>> {
+        let t = self.transpose();
+        (t.compose(self) == Self::identity()).then_some(t)
+    }
+}
+
+impl Transform<2> {
+    /// Rotate 90° clockwise on screen, where y points down.
+    pub fn rotate_cw() -> Self {
+        Self {
+            m: [[0, -1], [1, 0]],
+        }
+    }
+
+    /// Rotate 90° counter-clockwise on screen, where y points down.
+    pub fn rotate_ccw() -> Self {
+        Self {
+            m: [[0, 1], [-1, 0]],
+        }
+    }
+
+    /// Rotate by `n` quarter turns, clockwise for positive `n` and counter-clockwise for negative.
+    pub fn rotate(n: i32) -> Self {
+        (0..n.rem_euclid(4)).fold(Self::identity(), |t, _| Self::rotate_cw().compose(&t))
+    }
+}
+
+impl Transform<3> {
+    /// The 24 rotations of 3D space that map axes onto axes, ie every way to orient a cube.  The
+    /// first is the identity.
+    pub fn rotations() -> Vec<Transform<3>> {
+        // each row picks a distinct axis, with a sign; the proper rotations are the ones whose
+        // determinant (permutation parity times the product of signs) is 1
+        let perms = [
+            ([0, 1, 2], 1),
+            ([0, 2, 1], -1),
+            ([1, 0, 2], -1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([2, 1, 0], -1),
+        ];
+        let mut rotations = Vec::with_capacity(24);
+        for (perm, parity) in perms {
+            for signs in 0..8 {
+                let sign = |i: usize| if signs & (1 << i) == 0 { 1 } else { -1 };
+                if parity * sign(0) * sign(1) * sign(2) != 1 {
+                    continue;
+                }
+                let mut m = [[0; 3]; 3];
+                for (i, row) in m.iter_mut().enumerate() {
+                    row[perm[i]] = sign(i);
+                }
+                rotations.push(Transform { m });
+            }
+        }
+        rotations
+    }
+}
+
+#[cfg(test)]
+mod transform_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::direction::CardDir;
+
+    #[test]
+    fn rotate_2d_test() {
+        let p: Point<2> = [3, 1].into();
+        let cw = Transform::rotate_cw();
+        assert_eq!(cw.apply(p), [-1, 3].into());
+        assert_eq!(Transform::rotate_ccw().apply(cw.apply(p)), p);
+        assert_eq!(Transform::rotate(4), Transform::identity());
+        assert_eq!(Transform::rotate(-1), Transform::rotate_ccw());
+        assert_eq!(Transform::rotate(2).apply(p), -p);
+
+        // rotating a direction's step matches turning the direction
+        for dir in CardDir::all() {
+            assert_eq!(cw.apply(dir.delta()), dir.cw().delta());
+        }
+    }
+
+    #[test]
+    fn pivot_test() {
+        let pivot: Point<2> = [5, 5].into();
+        let cw = Transform::rotate_cw();
+        assert_eq!(cw.apply_about(pivot, pivot), pivot);
+        assert_eq!(cw.apply_about([7, 5].into(), pivot), [5, 7].into());
+        let mut p: Point<2> = [6, 9].into();
+        for _ in 0..4 {
+            p = cw.apply_about(p, pivot);
+        }
+        assert_eq!(p, [6, 9].into());
+    }
+
+    #[test]
+    fn reflect_test() {
+        let p: Point<3> = [1, 2, 3].into();
+        assert_eq!(Transform::reflect(1).apply(p), [1, -2, 3].into());
+        for axis in 0..3 {
+            let r = Transform::<3>::reflect(axis);
+            assert_eq!(r.compose(&r), Transform::identity());
+            assert_eq!(r.inverse(), Some(r));
+        }
+        // a rotation and a reflection compose into a reflection across the diagonal
+        let diag = Transform::reflect(0).compose(&Transform::rotate_cw());
+        assert_eq!(diag.apply([3, 1].into()), [1, 3].into());
+    }
+
+    #[test]
+    fn inverse_test() {
+        for t in Transform::rotations() {
+            let inv = t.inverse().unwrap();
+            assert_eq!(t.compose(&inv), Transform::identity());
+            assert_eq!(inv.compose(&t), Transform::identity());
+        }
+        let stretch = Transform {
+            m: [[2, 0], [0, 1]],
+        };
+        assert_eq!(stretch.inverse(), None);
+        let skew = Transform {
+            m: [[1, 1], [0, 1]],
+        };
+        assert_eq!(skew.inverse(), None);
+    }
+
+    #[test]
+    fn rotations_3d_test() {
+        let rotations = Transform::rotations();
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(rotations[0], Transform::identity());
+
+        // a point with distinct, nonzero coordinates lands somewhere different for each
+        let p: Point<3> = [1, 2, 3].into();
+        let images: HashSet<Point<3>> = rotations.iter().map(|t| t.apply(p)).collect();
+        assert_eq!(images.len(), 24);
+
+        // the rotations are closed under composition, so form a group
+        let set: HashSet<Transform<3>> = rotations.iter().copied().collect();
+        for a in &rotations {
+            for b in &rotations {
+                assert!(set.contains(&a.compose(b)));
+            }
+        }
+
+        // and no reflection is among them
+        assert!(!set.contains(&Transform::reflect(2)));
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt::Display;
+
+use crate::{
+    grid::{Cell, Grid, GridLike},
+    point::Point,
+};
+
+/// A dense grid whose edges wrap around, like a torus.  Walking off the right edge lands on the
+/// left edge, walking off the top lands on the bottom, and so on.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WrappingGrid<T: Copy> {
+    pub grid: Grid<T>,
+}
+
+impl<T: Copy> WrappingGrid<T> {
+    pub fn new(grid: Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    /// Map any point onto its position within the grid bounds.  Panics if the grid is empty.
+    pub fn wrap(&self, p: Point<2>) -> Point<2> {
+        [
+            p.x().rem_euclid(self.grid.width() as i64),
+            p.y().rem_euclid(self.grid.height() as i64),
+        ]
+        .into()
+    }
+}
+
+impl<T: Copy> GridLike<T> for WrappingGrid<T> {
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Get data at the given point, wrapping it into the grid bounds first.  Only returns None
+    /// if the grid is empty.
+    fn getp(&self, p: Point<2>) -> Option<T> {
+        if self.grid.area() == 0 {
+            return None;
+        }
+        self.grid.getp(self.wrap(p))
+    }
+
+    /// Set data at the given point, wrapping it into the grid bounds first.  Panics if the grid
+    /// is empty.
+    fn setp(&mut self, p: Point<2>, new_data: T) {
+        assert!(self.grid.area() > 0, "can't set a cell in an empty grid");
+        let p = self.wrap(p);
+        self.grid.setp(p, new_data);
+    }
+
+    fn cellp(&self, p: Point<2>) -> Option<Cell<T>> {
+        if self.grid.area() == 0 {
+            return None;
+        }
+        let p = self.wrap(p);
+        self.grid.getp(p).map(|data| Cell::new(p, data))
+    }
+}
+
+impl<T: Copy> From<Grid<T>> for WrappingGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::new(grid)
+    }
+}
+
+impl<T> Display for WrappingGrid<T>
+where
+    T: Display + Copy,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+#[cfg(test)]
+mod wrapping_grid_tests {
+    use super::*;
+    use crate::sparse_grid::SparseGrid;
+
+    fn grid_3x3() -> Grid<u8> {
+        Grid {
+            #[rustfmt::skip]
+            cells: vec![
+                vec![1, 2, 3],
+                vec![4, 5, 6],
+                vec![7, 8, 9]
+            ],
+        }
+    }
+
+    #[test]
+    fn wrapping_getp_test() {
+        let g = WrappingGrid::new(grid_3x3());
+
+        assert_eq!(g.getp([-1, 0].into()), Some(3));
+        assert_eq!(g.getp([3, 3].into()), Some(1));
+        assert_eq!(g.getp([-4, -8].into()), Some(6));
+        assert_eq!(
+            WrappingGrid::<u8>::new(Grid::new(vec![])).getp([0, 0].into()),
+            None
+        );
+    }
+
+    #[test]
+    fn wrapping_adj_test() {
+        let g = WrappingGrid::new(grid_3x3());
+
+        #[rustfmt::skip]
+        assert_eq!(
+            g.adj_8([0, 0].into()).cells.map(|c| c.map(|c| c.data)),
+            [
+                Some(9), Some(7), Some(8),
+                Some(3),          Some(2),
+                Some(6), Some(4), Some(5),
+            ]
+        );
+        assert_eq!(
+            g.adj_4([2, 2].into()).right(),
+            Some(Cell::new([0, 2].into(), 7))
+        );
+    }
+
+    #[test]
+    fn wrapping_setp_test() {
+        let mut g = WrappingGrid::new(grid_3x3());
+        g.setp([5, -1].into(), 0);
+        assert_eq!(g.grid.get(2, 2), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't set a cell in an empty grid")]
+    fn wrapping_setp_empty_test() {
+        let mut g: WrappingGrid<u8> = WrappingGrid::new(Grid::new(vec![]));
+        g.setp([1, 1].into(), 0);
+    }
+
+    /// Count the neighbors of a point holding an even number, whatever the grid representation.
+    fn even_neighbors(g: &impl GridLike<u8>, p: Point<2>) -> usize {
+        g.adj_8(p)
+            .cells
+            .iter()
+            .flatten()
+            .filter(|c| c.data % 2 == 0)
+            .count()
+    }
+
+    #[test]
+    fn grid_like_test() {
+        let dense = grid_3x3();
+        let sparse = SparseGrid::from(&dense);
+        let wrapping = WrappingGrid::new(dense.clone());
+
+        assert_eq!(even_neighbors(&dense, [0, 0].into()), 2);
+        assert_eq!(even_neighbors(&sparse, [0, 0].into()), 2);
+        assert_eq!(even_neighbors(&wrapping, [0, 0].into()), 4);
+    }
+}